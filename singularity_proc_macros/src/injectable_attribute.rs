@@ -0,0 +1,97 @@
+﻿use crate::injectable_struct::InjectableStruct;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+
+/// `#[injectable(options)] struct ..` → the struct, cleaned of the inert
+/// attributes only the derive knows, plus the derived impls.
+///
+/// Unlike the derive it may rewrite the struct: a `#[inject(once = ..)]`
+/// field of plain type `T` becomes the `OnceCell<T>` its getter needs.
+pub(crate) fn expand(args: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let mut input: DeriveInput = parse2(item)?;
+    if !args.is_empty() {
+        input.attrs.push(parse_quote! { #[injectable(#args)] });
+    }
+
+    if let Data::Struct(data) = &mut input.data {
+        for field in data.fields.iter_mut().filter(|field| is_once(field) && !is_once_cell(&field.ty)) {
+            let ty = &field.ty;
+            field.ty = parse_quote! { ::core::cell::OnceCell<#ty> };
+        }
+    }
+
+    let impls = InjectableStruct::try_new(&input)?.into_token_stream();
+
+    // Nothing registers these outside the derive, left in they'd fail to resolve
+    input.attrs.retain(|attr| !attr.path().is_ident("injectable"));
+    match &mut input.data {
+        Data::Struct(data) => data.fields.iter_mut().for_each(strip_inject),
+        Data::Union(data) => data.fields.named.iter_mut().for_each(strip_inject),
+        Data::Enum(_) => {}
+    }
+
+    Ok(quote! {
+        #input
+        #impls
+    })
+}
+
+fn strip_inject(field: &mut Field) {
+    field.attrs.retain(|attr| !attr.path().is_ident("inject"));
+}
+
+/// `#[inject(once = ..)]`
+fn is_once(field: &Field) -> bool {
+    field.attrs.iter()
+        .filter(|attr| attr.path().is_ident("inject"))
+        .any(|attr| matches!(
+            attr.parse_args::<Expr>(),
+            Ok(Expr::Assign(assign)) if matches!(&*assign.left, Expr::Path(p) if p.path.is_ident("once"))
+        ))
+}
+
+fn is_once_cell(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "OnceCell"))
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn struct_is_re_emitted_without_inert_attributes() {
+        let code = expand(
+            quote! { singleton },
+            quote! {
+                struct Service {
+                    repo: Repo,
+                    #[inject(|| 3)]
+                    retries: u8,
+                }
+            },
+        ).unwrap().to_string();
+
+        assert!(code.starts_with("struct Service { repo : Repo , retries : u8 , }"), "{code}");
+        assert!(code.contains("const SCOPE : Scope = Scope :: Singleton ;"), "{code}");
+        assert!(!code.contains("# [inject"), "{code}");
+    }
+
+    #[test]
+    fn once_fields_are_wrapped_in_once_cells() {
+        let code = expand(
+            quote! {},
+            quote! {
+                struct Report {
+                    #[inject(once = || summarize())]
+                    summary: String,
+                }
+            },
+        ).unwrap().to_string();
+
+        assert!(code.contains("summary : :: core :: cell :: OnceCell < String >"), "{code}");
+        assert!(code.contains("fn summary (& self) -> & String"), "{code}");
+    }
+}
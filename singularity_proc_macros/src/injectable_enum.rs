@@ -0,0 +1,118 @@
+﻿use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::*;
+
+
+/// A two-variant enum, each variant wrapping one service; the registered
+/// `VariantSelector<Self>` names the one to build.
+pub(crate) struct InjectableEnum<'a> {
+    ident: &'a Ident,
+    variants: Vec<(&'a Ident, &'a Type)>,
+}
+
+impl<'a> InjectableEnum<'a> {
+    pub fn try_new(input: &'a DeriveInput) -> Result<Self> {
+        let ident = &input.ident;
+        let Data::Enum(data) = &input.data else {
+            return Err(Error::new_spanned(ident, "expected an enum"));
+        };
+        if let Some(attr) = input.attrs.iter().find(|a| a.path().is_ident("injectable") || a.path().is_ident("inject")) {
+            return Err(Error::new_spanned(attr, "an injectable enum takes no options"));
+        }
+        if !input.generics.params.is_empty() {
+            return Err(Error::new_spanned(&input.generics, "an injectable enum can't be generic"));
+        }
+        if data.variants.len() != 2 {
+            return Err(Error::new_spanned(ident, "an injectable enum needs exactly two variants"));
+        }
+
+        let variants = data.variants.iter()
+            .map(|variant| match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Ok((&variant.ident, &fields.unnamed[0].ty)),
+                _ => Err(Error::new_spanned(variant, "each variant must wrap exactly one service, `Variant(Service)`")),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { ident, variants })
+    }
+
+    /// Every variant's service is a `LazyDep`, only the selected one is built.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_token_stream(&self) -> TokenStream {
+        let ident = self.ident;
+        let names: Vec<_> = self.variants.iter().map(|(name, _)| name.to_string()).collect();
+        let variant_idents = self.variants.iter().map(|(name, _)| name);
+        let types = self.variants.iter().map(|(_, ty)| ty);
+        let bindings: Vec<_> = (0..self.variants.len()).map(|index| format_ident!("variant_{}", index)).collect();
+        let expected = names.join("`, `");
+        // The selector, then one `LazyDep` per variant
+        let dep_count = self.variants.len() + 1;
+
+        quote! {
+            impl Injectable for #ident {
+                type Deps = (Registered<VariantSelector<Self>>, #( LazyDep<#types> ),*);
+                const DEP_COUNT: usize = #dep_count;
+
+                fn inject((selector, #(#bindings),*): Self::Deps) -> Self {
+                    match selector.variant() {
+                        #( #names => Self::#variant_idents(#bindings.into_inner()), )*
+                        other => panic!(
+                            "no variant `{}` on `{}`, expected one of `{}`",
+                            other,
+                            stringify!(#ident),
+                            #expected,
+                        ),
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn selects_a_variant_by_name() {
+        let input: DeriveInput = parse_quote! {
+            enum Storage {
+                Disk(DiskStore),
+                Memory(MemoryStore),
+            }
+        };
+
+        let code = InjectableEnum::try_new(&input).unwrap().into_token_stream().to_string();
+
+        assert!(code.contains(
+            "type Deps = (Registered < VariantSelector < Self >> , LazyDep < DiskStore > , LazyDep < MemoryStore >) ;"
+        ), "{code}");
+        assert!(code.contains("\"Disk\" => Self :: Disk (variant_0 . into_inner ()) ,"), "{code}");
+    }
+
+    #[test]
+    fn rejects_variants_without_a_single_service() {
+        let input: DeriveInput = parse_quote! {
+            enum Storage {
+                Disk(DiskStore),
+                Memory { store: MemoryStore },
+            }
+        };
+
+        let err = InjectableEnum::try_new(&input).err().expect("must be rejected");
+        assert!(err.to_string().contains("exactly one service"), "{err}");
+    }
+
+    #[test]
+    fn rejects_other_variant_counts() {
+        let input: DeriveInput = parse_quote! {
+            enum Storage {
+                Disk(DiskStore),
+            }
+        };
+
+        let err = InjectableEnum::try_new(&input).err().expect("must be rejected");
+        assert!(err.to_string().contains("exactly two variants"), "{err}");
+    }
+}
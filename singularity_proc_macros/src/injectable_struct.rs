@@ -1,17 +1,53 @@
 ﻿use crate::struct_kind::StructKind;
+use crate::struct_options::StructOptions;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::DeriveInput;
 use syn::*;
 
+/// Largest `Deps` tuple `ResolveDepsFrom` is implemented for.
+const MAX_DEPENDENCIES: usize = 16;
+
+/// Struct fields split by how `inject` fills them.
+#[derive(Default)]
+struct Dependencies<'f> {
+    /// Element types of `Deps`, in order.
+    types: Vec<TokenStream>,
+    /// Fields resolved as themselves (not flattened), bounded under `strict`.
+    resolved: Vec<&'f Type>,
+    /// `let` statements building each flattened or aliased field from its dep.
+    flattened: Vec<TokenStream>,
+    /// Binding per dependency used to destructure `Deps`.
+    tokens: Vec<TokenStream>,
+    /// Named factory fields, `ident: expr`.
+    factory_tokens: Vec<TokenStream>,
+    /// Every field's initializer in declaration order, for tuple structs.
+    ordered: Vec<TokenStream>,
+    /// Some field is `#[inject(skip)]`.
+    skips: bool,
+    /// Some factory awaits, so `AsyncInjectable` is generated instead.
+    awaits: bool,
+    /// Getters filling `#[inject(once = ..)]` fields on first access.
+    getters: Vec<TokenStream>,
+}
+
 pub(crate) struct InjectableStruct<'a> {
     ident: &'a Ident,
     generics: &'a Generics,
     kind: StructKind<'a>,
+    options: StructOptions,
 }
 
 impl<'a> InjectableStruct<'a> {
+    /// Panicking shorthand for `try_new`, handy in unit tests.
+    #[cfg(test)]
     pub fn new(input: &'a DeriveInput) -> Self {
+        Self::try_new(input).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like `new`, but reports problems as spanned errors for the compiler.
+    pub fn try_new(input: &'a DeriveInput) -> Result<Self> {
         let ident = &input.ident;
         let generics = &input.generics;
 
@@ -21,14 +57,70 @@ impl<'a> InjectableStruct<'a> {
                 syn::Fields::Unnamed(fields) => StructKind::Unnamed(fields),
                 syn::Fields::Unit => StructKind::Unit,
             },
-            _ => panic!("Injectable can only be derived on structs."),
+            syn::Data::Union(data_union) => {
+                let injected = data_union.fields.named.iter()
+                    .filter(|field| field.attrs.iter().any(|a| a.path().is_ident("inject")))
+                    .count();
+                if injected != 1 {
+                    return Err(Error::new_spanned(
+                        ident,
+                        "a union needs exactly one `#[inject(...)]` field, the one it is built as",
+                    ));
+                }
+                StructKind::Union(&data_union.fields)
+            }
+            _ => return Err(Error::new_spanned(ident, "Injectable can only be derived on structs.")),
         };
 
-        InjectableStruct {
+        // Only fields read `inject`, anywhere else it would be silently ignored
+        if let Some(attr) = input.attrs.iter().find(|a| a.path().is_ident("inject")) {
+            return Err(Error::new_spanned(
+                attr,
+                "#[inject] only applies to fields, use #[injectable(...)] for struct options",
+            ));
+        }
+
+        let options = StructOptions::from_attrs(&input.attrs)?;
+
+        if let Some(base) = &options.base && !matches!(kind, StructKind::Named(_)) {
+            return Err(Error::new_spanned(base, "`base` is only supported on structs with named fields"));
+        }
+        if let Some(config) = &options.from_config && !matches!(kind, StructKind::Named(_)) {
+            return Err(Error::new_spanned(config, "`from_config` maps fields by name, it needs a struct with named fields"));
+        }
+
+        let injectable = InjectableStruct {
             ident,
             generics,
             kind,
+            options,
+        };
+
+        if injectable.options.transparent {
+            let [field] = injectable.fields()[..] else {
+                return Err(Error::new_spanned(ident, "`transparent` needs exactly one field"));
+            };
+            if let Some(attr) = field.attrs.iter().find(|a| a.path().is_ident("inject")) {
+                return Err(Error::new_spanned(attr, "a `transparent` struct's field is always resolved"));
+            }
+        }
+
+        Ok(injectable)
+    }
+
+    /// A `ResolveBundle`: named fields only, each of them resolved.
+    pub fn try_new_bundle(input: &'a DeriveInput) -> Result<Self> {
+        let syn::Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) = &input.data else {
+            return Err(Error::new_spanned(&input.ident, "ResolveBundle needs a struct with named fields, one per service"));
+        };
+        let factory = fields.named.iter()
+            .flat_map(|field| &field.attrs)
+            .find(|attr| attr.path().is_ident("inject"));
+        if let Some(attr) = factory {
+            return Err(Error::new_spanned(attr, "a bundle resolves every field, use #[derive(Injectable)] for factories"));
         }
+
+        Self::try_new(input)
     }
 
     fn fields(&self) -> Vec<&syn::Field> {
@@ -36,90 +128,321 @@ impl<'a> InjectableStruct<'a> {
             StructKind::Named(fields) => fields.named.iter().collect(),
             StructKind::Unnamed(fields) => fields.unnamed.iter().collect(),
             StructKind::Unit => vec![],
+            // The other fields are never written, so they aren't dependencies either
+            StructKind::Union(fields) => fields.named.iter()
+                .filter(|field| field.attrs.iter().any(|a| a.path().is_ident("inject")))
+                .collect(),
         }
     }
 
-    fn parse_dependencies(
-        &self,
-    ) -> (
-        Vec<&Type>,       // dep_types
-        Vec<TokenStream>, // dep_tokens
-        Vec<TokenStream>, // factory_tokens (named use ident: expr)
-        Vec<TokenStream>, // factory_exprs  (unnamed use expr only)
-    ) {
-        let mut dep_types = Vec::new();
-        let mut dep_tokens = Vec::new();
-        let mut factory_tokens = Vec::new();
-        let mut factory_exprs = Vec::new();
-
-        for field in self.fields() {
-            if let Some(attr) = field.attrs.iter().find(|a| a.path().is_ident("inject")) {
-                let expr: Expr = match attr.parse_args() {
-                    Ok(ex) => ex,
-                    Err(_) => {
-                        let tokens = attr.meta.require_list()
-                            .expect("expected #[inject(...)]")
-                            .tokens.clone();
-
-                        syn::parse2(tokens)
-                            .expect("expected valid closure like #[inject(|| expr)]")
-                    }
-                };
+    fn parse_dependencies(&self) -> Result<Dependencies<'_>> {
+        let mut deps = Dependencies::default();
 
-                let mut expr_ref = &expr;
-                while let Expr::Paren(paren) = expr_ref {
-                    expr_ref = &*paren.expr;
+        for (index, field) in self.fields().into_iter().enumerate() {
+            let inject_attr = field.attrs.iter().find(|a| a.path().is_ident("inject"));
+
+            let flatten = inject_attr.is_some_and(|attr| Self::is_flag(attr, "flatten"));
+            if let Some(attr) = inject_attr && flatten && matches!(self.kind, StructKind::Union(_)) {
+                return Err(Error::new_spanned(attr, "#[inject(flatten)] is not supported on unions"));
+            }
+
+            // Left out of `Self { .. }` entirely, filled from `Default`
+            if let Some(attr) = inject_attr && Self::is_flag(attr, "skip") {
+                if !matches!(self.kind, StructKind::Named(_)) {
+                    return Err(Error::new_spanned(attr, "#[inject(skip)] is only supported on named fields"));
                 }
+                deps.skips = true;
+                continue;
+            }
 
-                let factory_expr = match expr_ref {
-                    Expr::Closure(c) => quote! { (#c)() },
-                    _ => quote! { ::core::default::Default::default() },
-                };
+            // `#[inject(as = "PrimaryDb")]`: resolved from `PrimaryDb`'s registration
+            let alias = match inject_attr {
+                Some(attr) => Self::alias(attr)?,
+                None => None,
+            };
 
+            let once = match inject_attr {
+                Some(attr) => self.once_getter(field, attr)?,
+                None => None,
+            };
+
+            let factory_expr = if flatten || alias.is_some() {
+                None
+            } else if let Some(getter) = once {
+                // Starts out empty, the getter fills it
+                deps.getters.push(getter);
+                Some(quote! { ::core::cell::OnceCell::new() })
+            } else if let Some(attr) = inject_attr {
+                let (factory_expr, awaits) = self.parse_factory(attr)?;
+                deps.awaits |= awaits;
+                Some(factory_expr)
+            } else if Self::is_phantom_data(&field.ty) {
+                // Markers are never resolved, they are simply conjured
+                Some(quote! { ::core::marker::PhantomData })
+            } else {
+                None
+            };
+
+            if let Some(factory_expr) = factory_expr {
 
                 // Save raw expression for tuple struct constructor
-                factory_exprs.push(factory_expr.clone());
-
-                // Get field name (or derive if tuple)
-                let ident = match self.kind {
-                    StructKind::Named(_) => field.ident.as_ref().unwrap().clone(),
-                    StructKind::Unnamed(_) => {
-                        if let Type::Path(path) = &field.ty {
-                            let ty_ident = &path.path.segments.last().unwrap().ident;
-                            format_ident!("{}", self.to_snake_case(&ty_ident.to_string()))
-                        } else {
-                            panic!("Unsupported type for unnamed inject field");
-                        }
-                    }
-                    StructKind::Unit => continue,
-                };
+                deps.ordered.push(factory_expr.clone());
 
-                // Named: ident: expr → `b: (|| 4)()`
-                factory_tokens.push(quote! { #ident: #factory_expr });
+                // Named: ident: expr → `b: (|| 4)()`, tuples only need `ordered`
+                if let Some(ident) = &field.ident {
+                    deps.factory_tokens.push(quote! { #ident: #factory_expr });
+                }
+            } else if let Some(config) = &self.options.from_config {
+                // Moved out of the config's field of the same name
+                if let Some(attr) = inject_attr {
+                    let message = match alias {
+                        Some(_) => "`from_config` fields come from the config, they can't be resolved `as` a registration",
+                        None => "`from_config` fields come from the config, they can't be flattened",
+                    };
+                    return Err(Error::new_spanned(attr, message));
+                }
+                let ident = field.ident.as_ref().unwrap();
+                deps.factory_tokens.push(quote_spanned! { config.span()=> #ident: config.#ident });
             } else {
                 // Dependency case
-                dep_types.push(&field.ty);
-                dep_tokens.push(if let StructKind::Named(_) = self.kind {
+                let binding = if let StructKind::Named(_) = self.kind {
                     let ident = field.ident.as_ref().unwrap();
                     quote! { #ident }
-                } else if let Type::Path(path) = &field.ty {
-                    let ty_ident = &path.path.segments.last().unwrap().ident;
-                    let ident = format_ident!("{}", self.to_snake_case(&ty_ident.to_string()));
+                } else if self.options.transparent {
+                    quote! { inner }
+                } else {
+                    // Unnamed fields go by position, two may well share a type
+                    let ident = format_ident!("field_{}", index);
                     quote! { #ident }
+                };
+                let ty = &field.ty;
+                if let Some(key) = &alias {
+                    deps.types.push(quote_spanned! { key.span()=> Registered<#key> });
+                    deps.flattened.push(quote_spanned! { key.span()=>
+                        let #binding: #ty = ::core::convert::Into::into(#binding.into_inner());
+                    });
+                } else if flatten {
+                    // Its deps stand in for it, `inject` then builds it in place
+                    deps.types.push(quote_spanned! { ty.span()=> <#ty as Injectable>::Deps });
+                    deps.flattened.push(quote! { let #binding = <#ty as Injectable>::inject(#binding); });
                 } else {
-                    panic!("Unsupported type format for unnamed DI");
-                });
+                    deps.types.push(quote! { #ty });
+                    deps.resolved.push(ty);
+                }
+                deps.ordered.push(binding.clone());
+                deps.tokens.push(binding);
+            }
+        }
+
+        // The config is the one thing resolved, every mapped field reads from it
+        if let Some(config) = &self.options.from_config {
+            deps.types.push(quote! { #config });
+            deps.resolved.push(config);
+            deps.tokens.push(quote_spanned! { config.span()=> config });
+        }
+
+        Ok(deps)
+    }
+
+    /// The key type of `#[inject(as = "PrimaryDb")]`, `None` for other attributes.
+    fn alias(attr: &Attribute) -> Result<Option<Type>> {
+        let starts_with_as = attr.meta.require_list().ok()
+            .and_then(|list| list.tokens.clone().into_iter().next())
+            .is_some_and(|first| matches!(first, proc_macro2::TokenTree::Ident(ident) if ident == "as"));
+        if !starts_with_as {
+            return Ok(None);
+        }
+
+        let mut key = None;
+        attr.parse_nested_meta(|meta| {
+            if !meta.path.is_ident("as") {
+                return Err(meta.error("expected `as`"));
             }
+            let name: LitStr = meta.value()?.parse()?;
+            key = Some(name.parse()?);
+            Ok(())
+        })?;
+        Ok(key)
+    }
+
+    /// `#[inject(skip)]`, `#[inject(flatten)]`
+    fn is_flag(attr: &Attribute, flag: &str) -> bool {
+        attr.parse_args::<Ident>().is_ok_and(|ident| ident == flag)
+    }
+
+    /// `#[inject(once = || compute())]` on `field: OnceCell<T>` → a getter named
+    /// after the field, running the closure on first access only.
+    fn once_getter(&self, field: &Field, attr: &Attribute) -> Result<Option<TokenStream>> {
+        let Ok(Expr::Assign(assign)) = attr.parse_args::<Expr>() else {
+            return Ok(None);
+        };
+        if !matches!(&*assign.left, Expr::Path(p) if p.path.is_ident("once")) {
+            return Ok(None);
         }
 
-        (dep_types, dep_tokens, factory_tokens, factory_exprs)
+        let Some(ident) = &field.ident else {
+            return Err(Error::new_spanned(attr, "`once` needs a named field, its getter takes the field's name"));
+        };
+        let value_type = match &field.ty {
+            Type::Path(path) => path.path.segments.last()
+                .filter(|segment| segment.ident == "OnceCell")
+                .and_then(|segment| match &segment.arguments {
+                    PathArguments::AngleBracketed(args) => args.args.first(),
+                    _ => None,
+                })
+                .and_then(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                }),
+            _ => None,
+        };
+        let Some(value_type) = value_type else {
+            return Err(Error::new_spanned(&field.ty, "a `once` field must be declared as `OnceCell<T>`"));
+        };
+
+        let vis = &field.vis;
+        let init = &assign.right;
+        Ok(Some(quote! {
+            /// Computed on first access, then kept.
+            #vis fn #ident(&self) -> &#value_type {
+                self.#ident.get_or_init(#init)
+            }
+        }))
     }
 
+    /// Turns `#[inject(...)]` into the expression initializing the field,
+    /// and whether that expression awaits.
+    fn parse_factory(&self, attr: &Attribute) -> Result<(TokenStream, bool)> {
+        let expr: Expr = match attr.parse_args() {
+            Ok(ex) => ex,
+            Err(_) => {
+                let tokens = attr.meta.require_list()
+                    .expect("expected #[inject(...)]")
+                    .tokens.clone();
+
+                syn::parse2(tokens)
+                    .expect("expected valid closure like #[inject(|| expr)]")
+            }
+        };
+
+        // Under `try` user factories return `Result`, unwrapped with `?`
+        let fallible = self.options.error.as_ref().map(|_| quote! { ? });
+
+        let mut expr_ref = &expr;
+        while let Expr::Paren(paren) = expr_ref {
+            expr_ref = &*paren.expr;
+        }
+
+        match expr_ref {
+            // `env = "DATABASE_URL"` → the variable parsed into the field's type
+            Expr::Assign(assign) if matches!(&*assign.left, Expr::Path(p) if p.path.is_ident("env")) => {
+                self.env_factory(&assign.right).map(|factory| (factory, false))
+            }
+            // The factory is called with no arguments, anything else can't typecheck
+            Expr::Closure(c) if !c.inputs.is_empty() => Err(Error::new_spanned(
+                c,
+                match self.kind {
+                    StructKind::Unnamed(_) => "inject factory for an unnamed field must take no arguments",
+                    _ => "inject factory must take no arguments",
+                },
+            )),
+            // `async || load().await` → `(async || load().await)().await`
+            Expr::Closure(c) if c.asyncness.is_some() => Ok((quote! { (#c)().await #fallible }, true)),
+            Expr::Closure(c) => Ok((quote! { (#c)() #fallible }, false)),
+            Expr::Async(block) => Ok((quote! { #block.await #fallible }, true)),
+            // `#[inject(default)]` spells out the fallback below
+            Expr::Path(p) if p.path.is_ident("default") => Ok((quote! { ::core::default::Default::default() }, false)),
+            // Free function factory: `#[inject(make_config)]` → `make_config()`
+            Expr::Path(p) => Ok((quote! { #p() #fallible }, false)),
+            // `#[inject("localhost")]` fills `&str` and `String` fields alike
+            Expr::Lit(ExprLit { lit: Lit::Str(lit), .. }) => Ok((quote! { ::core::convert::Into::into(#lit) }, false)),
+            // `#[inject(42)]`, `#[inject(true)]` → the literal itself
+            Expr::Lit(lit) => Ok((quote! { #lit }, false)),
+            _ => Ok((quote! { ::core::default::Default::default() }, false)),
+        }
+    }
+
+    /// Reads an environment variable through `FromStr`; unset reads as `""`,
+    /// which only a `String` field accepts, any other type panics naming the
+    /// unset variable. Under `try` a parse error is `?`-ed into the struct's
+    /// error type.
+    #[cfg(feature = "std")]
+    fn env_factory(&self, var: &Expr) -> Result<TokenStream> {
+        let Expr::Lit(ExprLit { lit: Lit::Str(var), .. }) = var else {
+            return Err(Error::new_spanned(var, "expected the variable name, `env = \"DATABASE_URL\"`"));
+        };
+
+        Ok(match self.options.error {
+            Some(_) => quote! { ::std::env::var(#var).unwrap_or_default().parse()? },
+            None => {
+                let unset = format!("environment variable `{}` is not set", var.value());
+                let invalid = format!("environment variable `{}` doesn't parse", var.value());
+                quote! {{
+                    let value = ::std::env::var(#var);
+                    value.as_deref().unwrap_or_default().parse().unwrap_or_else(|_| match value {
+                        ::core::result::Result::Err(::std::env::VarError::NotPresent) => panic!(#unset),
+                        _ => panic!(#invalid),
+                    })
+                }}
+            }
+        })
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn env_factory(&self, var: &Expr) -> Result<TokenStream> {
+        Err(Error::new_spanned(var, "`env` factories need the `std` feature"))
+    }
+
+    /// Detects `PhantomData<_>` by its last path segment.
+    fn is_phantom_data(ty: &Type) -> bool {
+        match ty {
+            Type::Path(path) => path.path.segments.last()
+                .is_some_and(|segment| segment.ident == "PhantomData"),
+            _ => false,
+        }
+    }
+
+    #[allow(clippy::wrong_self_convention)]
     pub fn into_token_stream(&self) -> TokenStream {
         let ident = self.ident;
-        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
 
-        let (dep_types, dep_tokens, factory_tokens, factory_exprs) = self.parse_dependencies();
+        let Dependencies {
+            types: dep_types,
+            resolved,
+            flattened,
+            tokens: dep_tokens,
+            factory_tokens,
+            ordered,
+            skips,
+            awaits,
+            getters,
+        } = match self.parse_dependencies() {
+            Ok(parsed) => parsed,
+            Err(err) => return err.to_compile_error(),
+        };
+
+        // Past this the tuple can't resolve, and rustc would only say so much less clearly
+        if dep_types.len() > MAX_DEPENDENCIES {
+            return Error::new_spanned(
+                ident,
+                format!(
+                    "Injectable supports up to {MAX_DEPENDENCIES} dependencies; {ident} has {}",
+                    dep_types.len(),
+                ),
+            )
+            .to_compile_error();
+        }
+
+        // Strict: every dependency must be `Injectable` itself, checked at the
+        // field (the span points there) instead of deep inside resolution
+        let mut generics = self.generics.clone();
+        if self.options.strict {
+            let where_clause = generics.make_where_clause();
+            for ty in &resolved {
+                where_clause.predicates.push(parse_quote_spanned! { ty.span()=> #ty: Injectable });
+            }
+        }
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
         let inject_params = if dep_tokens.is_empty() {
             quote! { _: Self::Deps }   // correctly ignore dependency list
@@ -127,62 +450,226 @@ impl<'a> InjectableStruct<'a> {
             quote! { ( #(#dep_tokens),* ): Self::Deps }
         };
 
-        match self.kind {
+        // Left out entirely without a scope flag, so the trait default applies
+        let scope = self.options.scope.as_ref().map(|variant| quote! {
+            const SCOPE: Scope = Scope::#variant;
+        });
+
+        // Awaiting factories only fit `AsyncInjectable::inject_async`,
+        // `try` wraps the body in `Ok` and names the error type
+        let (trait_ident, inject_sig, output, error_type) = match (&self.options.error, awaits) {
+            (Some(_), true) => {
+                return Error::new_spanned(self.ident, "`try` can't be combined with async factories")
+                    .to_compile_error();
+            }
+            (Some(error), false) => (
+                quote! { TryInjectable },
+                quote! { fn try_inject },
+                quote! { ::core::result::Result<Self, #error> },
+                Some(quote! { type Error = #error; }),
+            ),
+            (None, true) => (quote! { AsyncInjectable }, quote! { async fn inject_async }, quote! { Self }, None),
+            (None, false) => (quote! { Injectable }, quote! { fn inject }, quote! { Self }, None),
+        };
+
+        let body = match self.kind {
             StructKind::Named(_) => {
                 let mut tokens = Vec::new();
-                tokens.extend(dep_tokens.iter().cloned());
+                // Under `from_config` the one binding is the config, not a field
+                if self.options.from_config.is_none() {
+                    tokens.extend(dep_tokens.iter().cloned());
+                }
                 tokens.extend(factory_tokens.iter().cloned());
 
-                quote! {
-                    impl #impl_generics Injectable for #ident #ty_generics #where_clause {
-                        type Deps = ( #(#dep_types),* );
-                        fn inject(#inject_params) -> Self {
-                            Self { #(#tokens),* }
-                        }
+                // Remaining fields come from the `base` option or the struct's own `Default`
+                if let Some(base) = &self.options.base {
+                    quote! { Self { #(#tokens,)* ..#base } }
+                } else if skips {
+                    quote! { Self { #(#tokens,)* ..::core::default::Default::default() } }
+                } else {
+                    quote! { Self { #(#tokens),* } }
+                }
+            }
+
+            // Positional, so initializers must stay in declaration order
+            StructKind::Unnamed(_) => quote! { Self( #(#ordered),* ) },
+
+            StructKind::Unit => quote! { Self },
+
+            // Writing a union field is safe, only reading one isn't
+            StructKind::Union(_) => quote! { Self { #(#factory_tokens),* } },
+        };
+        let body = match error_type {
+            Some(_) => quote! { ::core::result::Result::Ok(#body) },
+            None => body,
+        };
+
+        let deps_type = quote! { ( #(#dep_types),* ) };
+        // Factory fields aren't counted, they aren't in `Deps`
+        let dep_count = dep_types.len();
+
+        let injectable_impl = quote! {
+            impl #impl_generics #trait_ident for #ident #ty_generics #where_clause {
+                type Deps = #deps_type;
+                #error_type
+                #scope
+                const DEP_COUNT: usize = #dep_count;
+                // Same performance intent as the `injectable!` macro
+                #[inline(always)]
+                #inject_sig(#inject_params) -> #output {
+                    #(#flattened)*
+                    #body
+                }
+            }
+        };
+
+        // Standard-library spelling of fallible construction
+        let try_from = self.options.error.as_ref().map(|error| quote! {
+            impl #impl_generics ::core::convert::TryFrom<#deps_type> for #ident #ty_generics #where_clause {
+                type Error = #error;
+
+                #[inline(always)]
+                fn try_from(deps: #deps_type) -> ::core::result::Result<Self, #error> {
+                    <Self as TryInjectable>::try_inject(deps)
+                }
+            }
+        });
+
+        // `Default` stands in for `inject(())`, so there must be nothing to resolve,
+        // and skipped fields would fill from this very impl
+        let default_impl = match &self.options.derive_default {
+            Some(option) if !dep_types.is_empty() => {
+                return Error::new_spanned(option, "`derive_default` needs a struct without dependencies")
+                    .to_compile_error();
+            }
+            Some(option) if skips => {
+                return Error::new_spanned(option, "`derive_default` can't fill `#[inject(skip)]` fields, they come from `Default`")
+                    .to_compile_error();
+            }
+            Some(option) if awaits || error_type.is_some() => {
+                return Error::new_spanned(option, "`derive_default` needs a plain `Injectable`, not an async or `try` one")
+                    .to_compile_error();
+            }
+            Some(_) => Some(quote! {
+                impl #impl_generics ::core::default::Default for #ident #ty_generics #where_clause {
+                    #[inline(always)]
+                    fn default() -> Self {
+                        <Self as Injectable>::inject(())
                     }
                 }
+            }),
+            None => None,
+        };
+
+        // A fresh container per call caches nothing, which would quietly
+        // break a singleton's one-instance promise
+        let self_resolve = match &self.options.self_resolve {
+            Some(option) if matches!(&self.options.scope, Some(scope) if scope == "Singleton" || scope == "WeakSingleton") => {
+                return Error::new_spanned(option, "`self_resolve` builds a fresh container per call, a singleton would never be shared")
+                    .to_compile_error();
+            }
+            Some(option) if awaits || error_type.is_some() => {
+                return Error::new_spanned(option, "`self_resolve` needs a plain `Injectable`, not an async or `try` one")
+                    .to_compile_error();
+            }
+            Some(option) if !self.generics.params.is_empty() => {
+                return Error::new_spanned(option, "`self_resolve` needs a struct without generics")
+                    .to_compile_error();
             }
+            Some(_) => Some(quote! {
+                impl #ident {
+                    /// Resolves the service from a fresh container, nothing is shared between calls.
+                    #[inline(always)]
+                    pub fn resolve() -> Self {
+                        Container::new().resolve::<Self>()
+                    }
+                }
+            }),
+            None => None,
+        };
 
-            StructKind::Unnamed(_) => {
-                let mut tokens = Vec::new();
-                tokens.extend(dep_tokens.iter().cloned());
-                tokens.extend(factory_exprs.iter().cloned());
-
-                quote! {
-                    impl #impl_generics Injectable for #ident #ty_generics #where_clause {
-                        type Deps = ( #(#dep_types),* );
-                        fn inject(#inject_params) -> Self {
-                            Self( #(#tokens),* )
-                        }
+        let clone_impl = match &self.options.clone {
+            Some(option) => match self.clone_impl(option) {
+                Ok(clone_impl) => Some(clone_impl),
+                Err(err) => return err.to_compile_error(),
+            },
+            None => None,
+        };
+
+        let constructor = self.options.constructor.as_ref().map(|ctor| {
+            let (asyncness, call) = if awaits {
+                (quote! { async }, quote! { <Self as AsyncInjectable>::inject_async(deps).await })
+            } else if self.options.error.is_some() {
+                (quote! {}, quote! { <Self as TryInjectable>::try_inject(deps) })
+            } else {
+                (quote! {}, quote! { <Self as Injectable>::inject(deps) })
+            };
+
+            quote! {
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// Builds the service from already resolved dependencies.
+                    #[inline(always)]
+                    pub #asyncness fn #ctor(deps: <Self as #trait_ident>::Deps) -> #output {
+                        #call
                     }
                 }
             }
+        });
 
-            StructKind::Unit => quote! {
-        impl #impl_generics Injectable for #ident #ty_generics #where_clause {
-            type Deps = ();
-            fn inject(_: Self::Deps) -> Self {
-                Self
+        let getters = (!getters.is_empty()).then(|| quote! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #(#getters)*
             }
+        });
+
+        quote! {
+            #injectable_impl
+            #getters
+            #try_from
+            #default_impl
+            #self_resolve
+            #clone_impl
+            #constructor
         }
     }
-        }
 
-    }
-    fn to_snake_case(&self, s: &str) -> String {
-        let mut result = String::new();
+    /// `impl Clone` cloning field by field, as `#[derive(Clone)]` would, but
+    /// bounded on the field types rather than on every type parameter.
+    fn clone_impl(&self, option: &Ident) -> Result<TokenStream> {
+        let ident = self.ident;
+        let fields = self.fields();
 
-        for (i, ch) in s.chars().enumerate() {
-            if ch.is_uppercase() {
-                if i != 0 {
-                    result.push('_');
-                }
-                result.push(ch.to_ascii_lowercase());
-            } else {
-                result.push(ch);
+        let body = match self.kind {
+            StructKind::Named(_) => {
+                let names = fields.iter().map(|field| &field.ident);
+                quote! { Self { #( #names: ::core::clone::Clone::clone(&self.#names) ),* } }
+            }
+            StructKind::Unnamed(_) => {
+                let indices = (0..fields.len()).map(Index::from);
+                quote! { Self( #( ::core::clone::Clone::clone(&self.#indices) ),* ) }
+            }
+            StructKind::Unit => quote! { Self },
+            StructKind::Union(_) => {
+                return Err(Error::new_spanned(option, "`clone` can't tell which union field to clone"));
             }
+        };
+
+        let mut generics = self.generics.clone();
+        let where_clause = generics.make_where_clause();
+        for field in &fields {
+            let ty = &field.ty;
+            where_clause.predicates.push(parse_quote! { #ty: ::core::clone::Clone });
         }
-        result
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        Ok(quote! {
+            impl #impl_generics ::core::clone::Clone for #ident #ty_generics #where_clause {
+                #[inline]
+                fn clone(&self) -> Self {
+                    #body
+                }
+            }
+        })
     }
 }
 
@@ -254,6 +741,35 @@ mod test {
         assert_eq!(result.ident.to_string(), "D");
     }
 
+    #[test]
+    fn union_is_built_as_its_injected_field() {
+        let input: DeriveInput = parse_quote! {
+            union Register {
+                raw: u32,
+                #[inject(|| [0u8; 4])]
+                bytes: [u8; 4],
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = ()"), "{code}");
+        assert!(code.contains("Self { bytes : (| | [0u8 ; 4]) () }"), "{code}");
+    }
+
+    #[test]
+    fn union_without_an_injected_field_is_rejected() {
+        let input: DeriveInput = parse_quote! {
+            union Register {
+                raw: u32,
+                bytes: [u8; 4],
+            }
+        };
+
+        let err = InjectableStruct::try_new(&input).err().expect("must be rejected");
+        assert!(err.to_string().contains("exactly one `#[inject(...)]` field"), "{err}");
+    }
+
     #[test]
     #[should_panic(expected = "Injectable can only be derived on structs.")] // örnek hata mesajı
     fn enum_not_supported() {
@@ -303,6 +819,570 @@ mod test {
 
 
 
+    #[test]
+    fn generated_inject_is_inlined() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Narrow {
+                a: i32,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("# [inline (always)]"), "inject must be inlined");
+    }
+
+    #[test]
+    fn phantom_data_is_defaulted_without_attribute() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Tagged<T> {
+                _marker: std::marker::PhantomData<T>,
+                inner: Inner,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (Inner)"), "PhantomData must not be a dependency");
+        assert!(
+            code.contains("_marker : :: core :: marker :: PhantomData"),
+            "PhantomData field must be conjured"
+        );
+    }
+
+    #[test]
+    fn factory_closure_with_arguments_is_rejected() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Counter(#[inject(|x| x + 1)] i32);
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("compile_error"), "An error must be emitted");
+        assert!(
+            code.contains("inject factory for an unnamed field must take no arguments"),
+            "Error must explain the closure arity"
+        );
+    }
+
+    #[test]
+    fn constructor_option_generates_inherent_method() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[injectable(constructor = "build")]
+            struct Service {
+                a: i32,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("impl Service"), "Inherent impl must be generated");
+        assert!(
+            code.contains("pub fn build (deps : < Self as Injectable > :: Deps) -> Self"),
+            "Named constructor must take the Deps"
+        );
+    }
+
+    #[test]
+    fn inject_on_the_struct_is_rejected() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[inject]
+            struct Service;
+        };
+
+        let err = InjectableStruct::try_new(&input).err().expect("attribute must be rejected");
+        assert!(err.to_string().contains("#[inject] only applies to fields"));
+    }
+
+    #[test]
+    fn unknown_option_is_rejected() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[injectable(frobnicate)]
+            struct Service;
+        };
+
+        let err = InjectableStruct::try_new(&input).err().expect("option must be rejected");
+        assert!(err.to_string().contains("unsupported injectable option"));
+    }
+
+    #[test]
+    fn scope_option_sets_the_scope_const() {
+        for (flag, variant) in [
+            ("singleton", "Singleton"),
+            ("weak_singleton", "WeakSingleton"),
+            ("transient", "Transient"),
+            ("scoped", "Scoped"),
+        ] {
+            let flag = syn::Ident::new(flag, proc_macro2::Span::call_site());
+            let input: syn::DeriveInput = parse_quote! {
+                #[injectable(#flag)]
+                struct Service {
+                    a: i32,
+                }
+            };
+
+            let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+            assert!(
+                code.contains(&format!("const SCOPE : Scope = Scope :: {variant} ;")),
+                "`{flag}` must set the scope const"
+            );
+        }
+    }
+
+    #[test]
+    fn scope_const_is_omitted_by_default() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Service;
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(!code.contains("SCOPE"), "Trait default must apply");
+    }
+
+    #[test]
+    fn conflicting_scope_options_are_rejected() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[injectable(singleton, transient)]
+            struct Service;
+        };
+
+        let err = InjectableStruct::try_new(&input).err().expect("options must be rejected");
+        assert!(err.to_string().contains("conflicting scope options"));
+    }
+
+    #[test]
+    fn async_factory_generates_async_injectable() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Service {
+                a: A,
+                #[inject(async || load().await)]
+                config: Config,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("impl AsyncInjectable for Service"), "Async trait must be implemented");
+        assert!(code.contains("async fn inject_async"), "inject must be async");
+        assert!(code.contains("config : (async | | load () . await) () . await"), "Factory must be awaited");
+        assert!(!code.contains("impl Injectable"), "Sync impl must not be generated");
+    }
+
+    #[test]
+    fn path_factory_is_called() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Service {
+                #[inject(config::make_config)]
+                config: Config,
+                #[inject(default)]
+                retries: u8,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("config : config :: make_config ()"), "Path factory must be called");
+        assert!(
+            code.contains("retries : :: core :: default :: Default :: default ()"),
+            "`default` must keep the Default fallback"
+        );
+    }
+
+    #[test]
+    fn skipped_fields_are_filled_from_default() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Service {
+                a: A,
+                #[inject(skip)]
+                later: Option<Handle>,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (A)"), "Skipped field must not be a dependency");
+        assert!(
+            code.contains("Self { a , .. :: core :: default :: Default :: default () }"),
+            "Skipped field must come from Default"
+        );
+    }
+
+    #[test]
+    fn skip_on_tuple_field_is_rejected() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Service(A, #[inject(skip)] u8);
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("only supported on named fields"));
+    }
+
+    #[test]
+    fn base_option_spreads_the_remaining_fields() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[injectable(base = "Self::template()")]
+            struct Service {
+                a: A,
+                #[inject(skip)]
+                b: u8,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("Self { a , .. Self :: template () }"), "Base must be spread");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn env_factory_parses_the_variable() {
+        let plain: DeriveInput = parse_quote! {
+            struct Config {
+                #[inject(env = "PORT")]
+                port: u16,
+            }
+        };
+        let fallible: DeriveInput = parse_quote! {
+            #[injectable(try, error = "ConfigError")]
+            struct Config {
+                #[inject(env = "PORT")]
+                port: u16,
+            }
+        };
+
+        let plain = InjectableStruct::new(&plain).into_token_stream().to_string();
+        let fallible = InjectableStruct::new(&fallible).into_token_stream().to_string();
+
+        assert!(plain.contains("let value = :: std :: env :: var (\"PORT\") ;"), "{plain}");
+        assert!(plain.contains("panic ! (\"environment variable `PORT` is not set\")"), "{plain}");
+        assert!(plain.contains("panic ! (\"environment variable `PORT` doesn't parse\")"), "{plain}");
+        assert!(fallible.contains(". unwrap_or_default () . parse () ?"), "{fallible}");
+    }
+
+    #[test]
+    fn flattened_field_contributes_its_deps() {
+        let input: DeriveInput = parse_quote! {
+            struct Service {
+                #[inject(flatten)]
+                storage: Storage,
+                clock: Clock,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (< Storage as Injectable > :: Deps , Clock)"), "{code}");
+        assert!(code.contains("let storage = < Storage as Injectable > :: inject (storage) ; Self { storage , clock }"), "{code}");
+    }
+
+    #[test]
+    fn transparent_newtype_resolves_its_field() {
+        let input: DeriveInput = parse_quote! {
+            #[injectable(transparent)]
+            struct UserId(Uuid);
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (Uuid)"), "{code}");
+        assert!(code.contains("fn inject ((inner) : Self :: Deps) -> Self { Self (inner) }"), "{code}");
+    }
+
+    #[test]
+    fn transparent_needs_a_single_field() {
+        for input in [
+            parse_quote! { #[injectable(transparent)] struct Pair(A, B); },
+            parse_quote! { #[injectable(transparent)] struct Unit; },
+            parse_quote! { #[injectable(transparent)] struct Id(#[inject(|| 1)] u32); },
+        ] {
+            let input: DeriveInput = input;
+            assert!(InjectableStruct::try_new(&input).is_err(), "{}", input.ident);
+        }
+    }
+
+    #[test]
+    fn derive_default_delegates_to_inject() {
+        let input: DeriveInput = parse_quote! {
+            #[injectable(derive_default)]
+            struct Settings {
+                #[inject(|| 3)]
+                retries: u8,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("impl :: core :: default :: Default for Settings"), "{code}");
+        assert!(code.contains("< Self as Injectable > :: inject (())"), "{code}");
+    }
+
+    #[test]
+    fn derive_default_with_dependencies_is_rejected() {
+        let input: DeriveInput = parse_quote! {
+            #[injectable(derive_default)]
+            struct Service {
+                repo: Repo,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("compile_error"), "{code}");
+        assert!(code.contains("needs a struct without dependencies"), "{code}");
+    }
+
+    #[test]
+    fn clone_option_clones_field_by_field() {
+        let input: DeriveInput = parse_quote! {
+            #[injectable(clone)]
+            struct Service<T> {
+                repo: Rc<T>,
+                #[inject(|| 3)]
+                retries: u8,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains(
+            "impl < T > :: core :: clone :: Clone for Service < T > where Rc < T > : :: core :: clone :: Clone , u8 : :: core :: clone :: Clone"
+        ), "{code}");
+        assert!(code.contains("repo : :: core :: clone :: Clone :: clone (& self . repo)"), "{code}");
+    }
+
+    #[test]
+    fn self_resolve_builds_from_a_fresh_container() {
+        let input: DeriveInput = parse_quote! {
+            #[injectable(self_resolve)]
+            struct Service {
+                repo: Repo,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("impl Service"), "{code}");
+        assert!(code.contains("pub fn resolve () -> Self { Container :: new () . resolve :: < Self > () }"), "{code}");
+    }
+
+    #[test]
+    fn self_resolve_on_singletons_is_rejected() {
+        for scope in ["singleton", "weak_singleton"] {
+            let scope = Ident::new(scope, proc_macro2::Span::call_site());
+            let input: DeriveInput = parse_quote! {
+                #[injectable(self_resolve, #scope)]
+                struct Service {
+                    repo: Repo,
+                }
+            };
+
+            let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+            assert!(code.contains("a singleton would never be shared"), "{scope}: {code}");
+        }
+    }
+
+    #[test]
+    fn from_config_maps_fields_by_name() {
+        let input: DeriveInput = parse_quote! {
+            #[injectable(from_config = "ServerConfig")]
+            struct Server {
+                host: String,
+                port: u16,
+                #[inject(|| 3)]
+                retries: u8,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (ServerConfig)"), "{code}");
+        assert!(code.contains("fn inject ((config) : Self :: Deps)"), "{code}");
+        assert!(code.contains("host : config . host , port : config . port , retries : (| | 3) ()"), "{code}");
+    }
+
+    #[test]
+    fn from_config_needs_named_fields() {
+        let input: DeriveInput = parse_quote! {
+            #[injectable(from_config = "ServerConfig")]
+            struct Server(String, u16);
+        };
+
+        let err = InjectableStruct::try_new(&input).err().expect("tuple structs are rejected");
+
+        assert!(err.to_string().contains("needs a struct with named fields"), "{err}");
+    }
+
+    #[test]
+    fn bundle_resolves_every_field() {
+        let input: DeriveInput = parse_quote! {
+            struct AppServices {
+                db: Database,
+                cache: Cache,
+            }
+        };
+
+        let code = InjectableStruct::try_new_bundle(&input).unwrap().into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (Database , Cache)"), "{code}");
+        assert!(code.contains("Self { db , cache }"), "{code}");
+    }
+
+    #[test]
+    fn bundle_rejects_factories_and_tuple_structs() {
+        let inputs: [DeriveInput; 2] = [
+            parse_quote! { struct AppServices { db: Database, #[inject(|| 3)] retries: u8 } },
+            parse_quote! { struct AppServices(Database, Cache); },
+        ];
+
+        for input in inputs {
+            let err = InjectableStruct::try_new_bundle(&input).err().expect("rejected");
+            assert!(err.to_string().contains("bundle") || err.to_string().contains("ResolveBundle"), "{err}");
+        }
+    }
+
+    #[test]
+    fn default_type_parameters_are_left_to_the_struct() {
+        let input: DeriveInput = parse_quote! {
+            struct Repo<T: Backend = DefaultBackend> {
+                backend: T,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        // Defaults aren't allowed on impls, `Repo` alone still names `Repo<DefaultBackend>`
+        assert!(code.contains("impl < T : Backend > Injectable for Repo < T >"), "{code}");
+        assert!(code.contains("type Deps = (T)"), "{code}");
+        assert!(!code.contains("DefaultBackend"), "{code}");
+    }
+
+    #[test]
+    fn once_fields_get_a_memoizing_getter() {
+        let input: DeriveInput = parse_quote! {
+            struct Report {
+                #[inject(once = || expensive())]
+                pub summary: OnceCell<String>,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("summary : :: core :: cell :: OnceCell :: new ()"), "{code}");
+        assert!(code.contains("pub fn summary (& self) -> & String { self . summary . get_or_init (| | expensive ()) }"), "{code}");
+    }
+
+    #[test]
+    fn once_fields_must_be_once_cells() {
+        let input: DeriveInput = parse_quote! {
+            struct Report {
+                #[inject(once = || expensive())]
+                summary: String,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("must be declared as `OnceCell<T>`"), "{code}");
+    }
+
+    #[test]
+    fn aliased_fields_resolve_their_key_registration() {
+        let input: DeriveInput = parse_quote! {
+            struct Replicated {
+                #[inject(as = "PrimaryDb")]
+                primary: Database,
+                #[inject(as = "ReplicaDb")]
+                replica: Database,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (Registered < PrimaryDb > , Registered < ReplicaDb >)"), "{code}");
+        assert!(code.contains("let primary : Database = :: core :: convert :: Into :: into (primary . into_inner ()) ;"), "{code}");
+        assert!(code.contains("Self { primary , replica }"), "{code}");
+    }
+
+    #[test]
+    fn aliased_tuple_fields_are_bound_by_position() {
+        let input: DeriveInput = parse_quote! {
+            struct Replicated(#[inject(as = "PrimaryDb")] Database, #[inject(as = "ReplicaDb")] Database);
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("let field_1 : Database = :: core :: convert :: Into :: into (field_1 . into_inner ()) ;"), "{code}");
+        assert!(code.contains("Self (field_0 , field_1)"), "{code}");
+    }
+
+    #[test]
+    fn aliases_reject_other_keys() {
+        let input: DeriveInput = parse_quote! {
+            struct Replicated {
+                #[inject(as = "PrimaryDb", from = "ReplicaDb")]
+                primary: Database,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("expected `as`"), "{code}");
+        assert!(!code.contains("Registered < ReplicaDb >"), "{code}");
+    }
+
+    #[test]
+    fn factories_see_the_struct_type_parameters() {
+        let input: DeriveInput = parse_quote! {
+            struct Buffer<T> {
+                #[inject(|| Vec::<T>::new())]
+                items: Vec<T>,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        // Emitted as written inside the generic impl, where `T` is in scope
+        assert!(code.contains("impl < T > Injectable for Buffer < T >"), "{code}");
+        assert!(code.contains("items : (| | Vec :: < T > :: new ()) ()"), "{code}");
+    }
+
+    #[test]
+    fn literal_factories_are_used_as_is() {
+        let input: DeriveInput = parse_quote! {
+            struct Endpoint {
+                #[inject("localhost")]
+                host: String,
+                #[inject(8080)]
+                port: u16,
+                #[inject(true)]
+                tls: bool,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("host : :: core :: convert :: Into :: into (\"localhost\")"), "{code}");
+        assert!(code.contains("port : 8080"), "{code}");
+        assert!(code.contains("tls : true"), "{code}");
+    }
+
+    #[test]
+    fn const_generics_are_kept_in_the_impl() {
+        let input: DeriveInput = parse_quote! {
+            struct Ring<const N: usize>([Slot; N], #[inject(|| [0u8; N])] [u8; N]);
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("impl < const N : usize > Injectable for Ring < N >"), "{code}");
+        assert!(code.contains("type Deps = ([Slot ; N])"), "{code}");
+        assert!(code.contains("Self (field_0 , (| | [0u8 ; N]) ())"), "{code}");
+    }
+
     #[test]
     fn generated_impl_for_generic_struct() {
         let input: syn::DeriveInput = parse_quote! {
@@ -311,7 +1391,7 @@ mod test {
         }
     };
 
-        let tokens =  &InjectableStruct::new(&input.into()).into_token_stream();
+        let tokens =  &InjectableStruct::new(&input).into_token_stream();
         let code = tokens.to_string();
 
 
@@ -339,4 +1419,89 @@ mod test {
             "Field initialization incorrect"
         );
     }
+
+    #[test]
+    fn strict_option_bounds_every_dependency() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[injectable(strict)]
+            struct Service<T> {
+                a: A,
+                b: T,
+                #[inject(|| 1)]
+                c: i32,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("where A : Injectable , T : Injectable"), "Deps must be bounded");
+        assert!(!code.contains("i32 : Injectable"), "Factory fields must not be bounded");
+    }
+
+    #[test]
+    fn try_option_generates_try_injectable_and_try_from() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[injectable(try, error = "ConfigError")]
+            struct Service {
+                a: A,
+                #[inject(parse_port)]
+                port: u16,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("impl TryInjectable for Service"), "Fallible trait must be implemented");
+        assert!(code.contains("type Error = ConfigError ;"), "Error type must be declared");
+        assert!(
+            code.contains("fn try_inject ((a) : Self :: Deps) -> :: core :: result :: Result < Self , ConfigError >"),
+            "try_inject must return the declared error"
+        );
+        assert!(code.contains("port : parse_port () ?"), "Factory results must be unwrapped");
+        assert!(
+            code.contains("impl :: core :: convert :: TryFrom < (A) > for Service"),
+            "TryFrom must take the Deps"
+        );
+    }
+
+    #[test]
+    fn try_option_requires_an_error_type() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[injectable(try)]
+            struct Service;
+        };
+
+        let err = InjectableStruct::try_new(&input).err().expect("option must be rejected");
+        assert!(err.to_string().contains("`try` needs the error type"));
+    }
+
+    #[test]
+    fn tuple_struct_keeps_field_order() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Foo(Dep, #[inject(|| 5)] i32, Dep2);
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(code.contains("type Deps = (Dep , Dep2)"), "Deps must skip the factory field");
+        assert!(code.contains("Self (field_0 , (| | 5) () , field_2)"), "Fields must keep declaration order");
+    }
+
+    #[test]
+    fn marker_only_generic_keeps_bounds_out_of_deps() {
+        let input: syn::DeriveInput = parse_quote! {
+            struct Encoder<T> where T: Codec {
+                _codec: PhantomData<T>,
+                inner: Inner,
+            }
+        };
+
+        let code = InjectableStruct::new(&input).into_token_stream().to_string();
+
+        assert!(
+            code.contains("impl < T > Injectable for Encoder < T > where T : Codec"),
+            "Where clause must carry over"
+        );
+        assert!(code.contains("type Deps = (Inner)"), "Marker generic must not be a dependency");
+    }
 }
\ No newline at end of file
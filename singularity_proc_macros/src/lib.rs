@@ -1,16 +1,111 @@
 
+mod injectable_attribute;
+mod injectable_enum;
 mod injectable_struct;
 mod struct_kind;
+mod struct_options;
 
+use injectable_enum::InjectableEnum;
 use injectable_struct::InjectableStruct;
 
 /// Basic derive proc macro for `Injectable`.
-#[proc_macro_derive(Injectable, attributes(inject))]
+///
+/// Field factories go in `#[inject(...)]`:
+/// - `|| expr` – closure, called with no arguments
+/// - `async || load().await` / `async { .. }` – awaited; the derive then implements
+///   `AsyncInjectable` instead, resolve it with `Container::resolve_async`
+/// - `make_config` – path to a `fn() -> T`, called
+/// - `42`, `true`, `"localhost"` – a literal, used as is; string literals go
+///   through `Into`, so they fill `String` fields as well as `&str`
+/// - `env = "DATABASE_URL"` – the environment variable (empty if unset) parsed
+///   into the field's type via `FromStr`; needs the `std` feature
+/// - `default` (or any other expression) – `Default::default()`
+/// - `as = "PrimaryDb"` – resolved as `Registered<PrimaryDb>` and converted
+///   with `Into`, so fields sharing a type (two `Database`s) can each come
+///   from their own registration, keyed by a newtype
+/// - `once = || compute()` (or `once = compute`) – on a `field: OnceCell<T>`:
+///   left empty, plus a `fn field(&self) -> &T` running it on first access only
+/// - `flatten` – on a field whose type is itself `Injectable`: its `Deps` take
+///   its place in the parent's `Deps` (as one nested tuple element), and it is
+///   built in place by its own `inject`
+/// - `skip` – left out of the constructor, filled by `..Default::default()`;
+///   the struct itself must implement `Default`
+///
+/// Struct-level options go in `#[injectable(...)]`:
+/// - `constructor = "name"` – also emit `pub fn name(deps) -> Self` delegating to `inject`
+/// - `base = "Self::template()"` – fill non-injected fields via `..Self::template()`;
+///   mark them `#[inject(skip)]` to keep them out of `Deps`
+/// - `singleton` / `weak_singleton` / `transient` / `scoped` – set `const SCOPE`
+///   (otherwise the trait default, `Scoped`, applies)
+/// - `strict` – require every dependency field to be `Injectable`, so a field
+///   missing its `#[inject(...)]` is reported at the field itself; other
+///   dependency kinds (`Registered<T>`, `Option<T>`, ...) are rejected
+/// - `try, error = "ConfigError"` – implement `TryInjectable` with that error,
+///   plus `TryFrom<Deps>`; closure and function factories return a `Result`
+///   whose error converts into it, and `?` is applied to each
+/// - `transparent` – for single-field newtypes: that field is the one
+///   dependency, `Self(inner)`, whatever its type looks like
+/// - `derive_default` – also implement `Default` as `inject(())`; only for
+///   structs without dependencies or `#[inject(skip)]` fields
+/// - `clone` – also implement `Clone` field by field, for services cloned out
+///   of a cache by value; each field type must be `Clone` (not on unions)
+/// - `from_config = "ServerConfig"` – for services filled from (usually
+///   deserialized) config: `Deps` is just `ServerConfig` and every field
+///   without `#[inject(...)]` takes the config's same-named field; named
+///   structs only
+/// - `self_resolve` – also emit `pub fn resolve() -> Self`, building the service
+///   from a fresh `Container`; rejected on singletons (nothing would be shared)
+///   and on generic, async or `try` services
+///
+/// The impl also sets `DEP_COUNT`, the number of `Deps` elements (factory
+/// fields aren't counted), for tooling to read at compile time.
+///
+/// `#[cfg(...)]` fields and `#[cfg_attr(..., inject(...))]` are evaluated by the
+/// compiler before the derive runs, so it only ever sees the enabled fields and
+/// attributes: a field gated off drops out of `Deps` entirely.
+///
+/// A union is built as its one `#[inject(...)]` field; the others are never
+/// written, so they need no factory.
+///
+/// Generics and where clauses carry over to the impl as written, minus any
+/// defaults: `struct Repo<T = Postgres>` still resolves as plain `Repo`. A parameter
+/// used only for typing (e.g. a codec) needs a `PhantomData<T>` field, which
+/// is filled in automatically and never becomes a dependency.
+///
+/// An enum of two variants, each wrapping one service, resolves as whichever
+/// variant the registered `VariantSelector<Storage>` names; only that service
+/// is built.
+///
+/// ```
+/// # use singularity::container::*;
+/// # singularity::injectable!(() => DiskStore);
+/// # singularity::injectable!(() => MemoryStore);
+/// #[derive(Injectable)]
+/// enum Storage {
+///     Disk(DiskStore),
+///     Memory(MemoryStore),
+/// }
+///
+/// # let container = Container::new();
+/// container.register_instance(VariantSelector::<Storage>::new("Memory"));
+/// # assert!(matches!(container.resolve::<Storage>(), Storage::Memory(_)));
+/// ```
+#[proc_macro_derive(Injectable, attributes(inject, injectable))]
 pub fn derive_injectable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
+    if let syn::Data::Enum(_) = input.data {
+        return match InjectableEnum::try_new(&input) {
+            Ok(injectable_enum) => injectable_enum.into_token_stream().into(),
+            Err(err) => err.to_compile_error().into(),
+        };
+    }
+
     // Create internal handler that extracts struct type, name, generics, fields, etc.
-    let injectable_struct = InjectableStruct::new(&input);
+    let injectable_struct = match InjectableStruct::try_new(&input) {
+        Ok(injectable_struct) => injectable_struct,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     // Generate final expanded code using strategy logic
     let expanded = injectable_struct.into_token_stream();
@@ -19,3 +114,60 @@ pub fn derive_injectable(input: proc_macro::TokenStream) -> proc_macro::TokenStr
 
     expanded.into()
 }
+
+/// Attribute form of `#[derive(Injectable)]`: struct options go in the
+/// attribute itself, field attributes work the same.
+///
+/// It owns the struct, so it can also rewrite it: a `#[inject(once = ..)]`
+/// field may be declared as plain `T`, it becomes `OnceCell<T>`.
+///
+/// ```
+/// # use singularity::container::*;
+/// # use singularity::container::attribute::injectable;
+/// # singularity::injectable!(() => Repo);
+/// # fn summarize() -> String { "all good".into() }
+/// #[injectable(singleton)]
+/// struct Report {
+///     repo: Repo,
+///     #[inject(once = || summarize())]
+///     summary: String,
+/// }
+///
+/// # let report = Container::new().resolve::<Report>();
+/// # assert_eq!(report.summary(), "all good");
+/// ```
+#[proc_macro_attribute]
+pub fn injectable(args: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match injectable_attribute::expand(args.into(), item.into()) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derives `Injectable` for a struct that only gathers services, resolving
+/// several at once: `container.resolve::<AppServices>()`.
+///
+/// Every named field is a dependency; `#[inject(...)]` factories are rejected,
+/// `#[derive(Injectable)]` is the one for mixing in plain values.
+///
+/// ```
+/// # use singularity::container::*;
+/// # singularity::injectable!(() => Database);
+/// # singularity::injectable!(() => Cache);
+/// #[derive(ResolveBundle)]
+/// struct AppServices {
+///     db: Database,
+///     cache: Cache,
+/// }
+///
+/// # let AppServices { db, cache } = Container::new().resolve::<AppServices>();
+/// ```
+#[proc_macro_derive(ResolveBundle, attributes(inject))]
+pub fn derive_resolve_bundle(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+    match InjectableStruct::try_new_bundle(&input) {
+        Ok(bundle) => bundle.into_token_stream().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
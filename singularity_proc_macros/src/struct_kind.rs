@@ -3,5 +3,7 @@
 pub (crate) enum StructKind<'a> {
     Named(&'a FieldsNamed),
     Unnamed(&'a FieldsUnnamed),
-    Unit
+    Unit,
+    /// A union starts out as its one `#[inject(...)]` field.
+    Union(&'a FieldsNamed),
 }
\ No newline at end of file
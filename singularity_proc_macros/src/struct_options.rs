@@ -0,0 +1,102 @@
+﻿use syn::{Attribute, Error, Expr, Ident, LitStr, Result, Type};
+
+
+/// Scope flags and the `Scope` variant each one selects.
+const SCOPES: [(&str, &str); 4] = [
+    ("singleton", "Singleton"),
+    ("weak_singleton", "WeakSingleton"),
+    ("transient", "Transient"),
+    ("scoped", "Scoped"),
+];
+
+/// Struct-level `#[injectable(...)]` options.
+#[derive(Default)]
+pub(crate) struct StructOptions {
+    /// `constructor = "build"` – inherent constructor delegating to `inject`.
+    pub constructor: Option<Ident>,
+    /// `base = "Self::template()"` – fills every field not injected via `..base`.
+    pub base: Option<Expr>,
+    /// `singleton` / `weak_singleton` / `transient` / `scoped` – the `Scope` variant for `const SCOPE`.
+    pub scope: Option<Ident>,
+    /// `strict` – bound every dependency field on `Injectable` in the generated impl.
+    pub strict: bool,
+    /// `transparent` – the struct's single field is its single dependency.
+    pub transparent: bool,
+    /// `try, error = "ConfigError"` – generate `TryInjectable` (and `TryFrom<Deps>`)
+    /// failing with this error type; factories return `Result` and are `?`-ed.
+    pub error: Option<Type>,
+    /// `derive_default` – also emit `impl Default` calling `inject(())`; kept as
+    /// the option's ident so misuse is reported there.
+    pub derive_default: Option<Ident>,
+    /// `self_resolve` – also emit `pub fn resolve() -> Self` on a fresh `Container`;
+    /// kept as the option's ident so misuse is reported there.
+    pub self_resolve: Option<Ident>,
+    /// `clone` – also emit `impl Clone`, field by field, bounded on each field
+    /// type being `Clone`; kept as the option's ident so misuse is reported there.
+    pub clone: Option<Ident>,
+    /// `from_config = "ServerConfig"` – the one dependency is this config type,
+    /// each field is moved out of its same-named field.
+    pub from_config: Option<Type>,
+}
+
+impl StructOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = StructOptions::default();
+        let mut fallible = None;
+
+        for attr in attrs.iter().filter(|a| a.path().is_ident("injectable")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("constructor") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    options.constructor = Some(name.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("base") {
+                    let base: LitStr = meta.value()?.parse()?;
+                    options.base = Some(base.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("try") {
+                    fallible = Some(attr);
+                    Ok(())
+                } else if meta.path.is_ident("error") {
+                    let error: LitStr = meta.value()?.parse()?;
+                    options.error = Some(error.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("from_config") {
+                    let config: LitStr = meta.value()?.parse()?;
+                    options.from_config = Some(config.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("derive_default") {
+                    options.derive_default = meta.path.get_ident().cloned();
+                    Ok(())
+                } else if meta.path.is_ident("self_resolve") {
+                    options.self_resolve = meta.path.get_ident().cloned();
+                    Ok(())
+                } else if meta.path.is_ident("clone") {
+                    options.clone = meta.path.get_ident().cloned();
+                    Ok(())
+                } else if meta.path.is_ident("transparent") {
+                    options.transparent = true;
+                    Ok(())
+                } else if meta.path.is_ident("strict") {
+                    options.strict = true;
+                    Ok(())
+                } else if let Some(variant) = SCOPES.iter().find(|(key, _)| meta.path.is_ident(key)) {
+                    if options.scope.is_some() {
+                        return Err(meta.error("conflicting scope options"));
+                    }
+                    options.scope = Some(Ident::new(variant.1, meta.path.segments[0].ident.span()));
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported injectable option"))
+                }
+            })?;
+        }
+
+        // `try` and `error` only make sense together
+        match (fallible, &options.error) {
+            (Some(attr), None) => Err(Error::new_spanned(attr, "`try` needs the error type: `error = \"MyError\"`")),
+            (None, Some(error)) => Err(Error::new_spanned(error, "`error` is only used together with `try`")),
+            _ => Ok(options),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::cell::Cell;
+
+use singularity::container::attribute::injectable;
+use singularity::container::*;
+
+
+struct Repo(i32);
+
+impl Injectable for Repo {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(7)
+    }
+}
+
+impl std::fmt::Debug for Repo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Repo({})", self.0)
+    }
+}
+
+thread_local! {
+    static SUMMARIES: Cell<u32> = const { Cell::new(0) };
+}
+
+fn summarize() -> String {
+    SUMMARIES.with(|runs| runs.set(runs.get() + 1));
+    "7 rows".into()
+}
+
+
+/// Docs and derives stay on the struct
+#[injectable(singleton, constructor = "build")]
+#[derive(Debug)]
+struct Report {
+    repo_id: Repo,
+    #[inject(|| 3)]
+    retries: u8,
+    #[inject(once = summarize)]
+    summary: String,
+}
+
+#[test]
+fn it_derives_through_the_attribute() {
+    let container = Container::new();
+    let report = container.resolve_ref::<Report>();
+
+    assert_eq!((report.repo_id.0, report.retries), (7, 3));
+    assert_eq!(Report::SCOPE, Scope::Singleton);
+    assert_eq!(Report::build(Repo(1)).repo_id.0, 1);
+    assert!(format!("{report:?}").contains("Repo(7)"));
+}
+
+
+#[test]
+fn it_wraps_plain_once_fields() {
+    let report = Container::new().resolve::<Report>();
+
+    let _: &std::cell::OnceCell<String> = &report.summary;
+    assert_eq!(report.summary(), "7 rows");
+    assert_eq!(report.summary(), "7 rows");
+    assert_eq!(SUMMARIES.with(Cell::get), 1);
+}
+
+
+#[injectable]
+struct Pair(Repo, #[inject(|| "pair")] &'static str);
+
+
+#[test]
+fn it_derives_tuple_structs_without_options() {
+    let Pair(repo, name) = Container::new().resolve::<Pair>();
+
+    assert_eq!((repo.0, name), (7, "pair"));
+}
@@ -0,0 +1,774 @@
+use std::marker::PhantomData;
+
+use singularity::container::*;
+
+
+struct Inner(i32);
+
+impl Injectable for Inner {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(7)
+    }
+}
+
+
+#[derive(Injectable)]
+struct Tagged<T: 'static> {
+    _marker: PhantomData<T>,
+    inner: Inner,
+}
+
+
+#[test]
+fn it_derives_phantom_markers_alongside_dependencies() {
+    let container = Container::new();
+
+    let tagged = container.resolve::<Tagged<String>>();
+    let _: PhantomData<String> = tagged._marker;
+    assert_eq!(tagged.inner.0, 7);
+}
+
+
+trait Codec {
+    const NAME: &'static str;
+}
+
+struct Json;
+
+impl Codec for Json {
+    const NAME: &'static str = "json";
+}
+
+#[derive(Injectable)]
+struct Encoder<T>
+where
+    T: Codec + 'static,
+{
+    _codec: PhantomData<T>,
+    inner: Inner,
+}
+
+impl<T: Codec> Encoder<T> {
+    fn describe(&self) -> String {
+        format!("{}-{}", T::NAME, self.inner.0)
+    }
+}
+
+
+#[test]
+fn it_derives_bounded_marker_generics() {
+    let encoder = Container::new().resolve::<Encoder<Json>>();
+
+    assert_eq!(encoder.describe(), "json-7");
+}
+
+
+#[derive(Injectable)]
+#[injectable(constructor = "build")]
+struct Built {
+    inner: Inner,
+    #[inject(|| 3)]
+    factor: i32,
+}
+
+
+#[test]
+fn it_derives_a_named_constructor() {
+    let built = Built::build(Inner(2));
+
+    assert_eq!(built.inner.0 * built.factor, 6);
+}
+
+
+struct Config {
+    url: &'static str,
+}
+
+fn make_config() -> Config {
+    Config { url: "postgres://localhost" }
+}
+
+
+#[derive(Injectable)]
+struct Database {
+    #[inject(make_config)]
+    config: Config,
+    inner: Inner,
+}
+
+
+#[test]
+fn it_derives_fields_from_factory_functions() {
+    let db = Container::new().resolve::<Database>();
+
+    assert_eq!(db.config.url, "postgres://localhost");
+    assert_eq!(db.inner.0, 7);
+}
+
+
+#[derive(Injectable)]
+struct Session {
+    inner: Inner,
+    #[inject(skip)]
+    token: Option<String>,
+}
+
+/// `#[inject(skip)]` needs the whole struct to be `Default`.
+impl Default for Session {
+    fn default() -> Self {
+        Session { inner: Inner(0), token: Some("anonymous".into()) }
+    }
+}
+
+
+#[test]
+fn it_derives_skipped_fields_from_the_struct_default() {
+    let session = Container::new().resolve::<Session>();
+
+    assert_eq!(session.inner.0, 7, "injected field must not be defaulted");
+    assert_eq!(session.token.as_deref(), Some("anonymous"));
+}
+
+
+#[derive(Injectable)]
+#[injectable(base = "Self::template()")]
+struct Server {
+    inner: Inner,
+    #[inject(skip)]
+    host: &'static str,
+    #[inject(skip)]
+    port: u16,
+}
+
+impl Server {
+    fn template() -> Self {
+        Server { inner: Inner(0), host: "0.0.0.0", port: 8080 }
+    }
+}
+
+
+#[test]
+fn it_derives_remaining_fields_from_a_base() {
+    let server = Container::new().resolve::<Server>();
+
+    assert_eq!(server.inner.0, 7);
+    assert_eq!(server.host, "0.0.0.0");
+    assert_eq!(server.port, 8080);
+}
+
+
+#[derive(Injectable)]
+#[injectable(singleton)]
+struct AppConfig {
+    inner: Inner,
+}
+
+
+#[test]
+fn it_derives_the_scope_from_the_struct_attribute() {
+    let container = Container::new();
+
+    assert_eq!(AppConfig::SCOPE, Scope::Singleton);
+    assert_eq!(Session::SCOPE, Scope::Scoped);
+    let config = container.resolve_ref::<AppConfig>();
+    // The `static-container` feature makes the container ignore scopes
+    if container.scope_of::<AppConfig>() == Scope::Singleton {
+        assert!(std::rc::Rc::ptr_eq(&config, &container.resolve_ref::<AppConfig>()));
+    }
+    assert_eq!(config.inner.0, 7);
+}
+
+
+// `test` stands in for any feature flag; it is always set for this target.
+#[derive(Injectable)]
+struct Metered {
+    inner: Inner,
+    #[cfg(test)]
+    metrics: Inner,
+    #[cfg(not(test))]
+    missing: NotInjectable,
+    #[cfg_attr(test, inject(|| 42))]
+    sample_rate: u32,
+}
+
+#[allow(dead_code)]
+struct NotInjectable;
+
+
+#[test]
+fn it_derives_with_cfg_gated_fields_and_attributes() {
+    let metered = Container::new().resolve::<Metered>();
+
+    assert_eq!(metered.inner.0, 7);
+    assert_eq!(metered.metrics.0, 7);
+    assert_eq!(metered.sample_rate, 42);
+}
+
+
+async fn load_endpoint() -> String {
+    "https://config".into()
+}
+
+#[derive(Injectable)]
+#[injectable(constructor = "build")]
+struct Remote {
+    inner: Inner,
+    #[inject(async || load_endpoint().await)]
+    endpoint: String,
+}
+
+/// Drives a future that never actually suspends, as every future here.
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+
+#[test]
+fn it_derives_async_factories() {
+    let remote = block_on(Container::new().resolve_async::<Remote>());
+    assert_eq!(remote.inner.0, 7);
+    assert_eq!(remote.endpoint, "https://config");
+
+    let built = block_on(Remote::build(Inner(1)));
+    assert_eq!(built.inner.0, 1);
+}
+
+
+struct Outer(i32);
+
+impl Injectable for Outer {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(9)
+    }
+}
+
+#[derive(Injectable)]
+struct Interleaved(Inner, #[inject(|| 5)] i32, Outer);
+
+
+#[test]
+fn it_derives_interleaved_tuple_fields_in_order() {
+    let Interleaved(inner, five, outer) = Container::new().resolve::<Interleaved>();
+
+    assert_eq!((inner.0, five, outer.0), (7, 5, 9));
+}
+
+
+#[derive(Injectable)]
+struct Pair(Inner, #[inject(|| 5)] i32, Inner);
+
+
+#[test]
+fn it_derives_tuple_fields_sharing_a_type() {
+    let Pair(first, five, second) = Container::new().resolve::<Pair>();
+
+    assert_eq!((first.0, five, second.0), (7, 5, 7));
+}
+
+
+#[derive(Debug, PartialEq)]
+struct ConfigError(String);
+
+fn parse_port() -> Result<u16, ConfigError> {
+    "8080".parse().map_err(|_| ConfigError("bad port".into()))
+}
+
+#[derive(Injectable)]
+#[injectable(try, error = "ConfigError")]
+struct Listener {
+    inner: Inner,
+    #[inject(parse_port)]
+    port: u16,
+    #[inject(|| if true { Ok(3) } else { Err(ConfigError("unreachable".into())) })]
+    backlog: u8,
+}
+
+#[derive(Debug, Injectable)]
+#[injectable(try, error = "ConfigError")]
+struct Rejecting(#[allow(dead_code)] #[inject(|| Err::<u8, _>(ConfigError("rejected".into())))] u8);
+
+
+#[test]
+fn it_derives_fallible_construction() {
+    let listener = Container::new().try_resolve::<Listener>().unwrap();
+    assert_eq!((listener.inner.0, listener.port, listener.backlog), (7, 8080, 3));
+
+    let listener: Listener = Inner(1).try_into().unwrap();
+    assert_eq!(listener.inner.0, 1);
+
+    let rejected: Result<Rejecting, _> = ().try_into();
+    assert_eq!(rejected.unwrap_err(), ConfigError("rejected".into()));
+}
+
+
+#[derive(Injectable)]
+struct Buffer<const N: usize> {
+    #[inject(|| [0u8; N])]
+    data: [u8; N],
+    inner: Inner,
+}
+
+#[derive(Injectable)]
+struct Ring<const N: usize>([Inner; N], #[inject(|| [N as u8; N])] [u8; N]);
+
+
+#[test]
+fn it_derives_const_generic_structs() {
+    let buffer = Container::new().resolve::<Buffer<16>>();
+    assert_eq!((buffer.data, buffer.inner.0), ([0; 16], 7));
+
+    let Ring(inners, slots) = Container::new().resolve::<Ring<3>>();
+    assert_eq!((inners.map(|inner| inner.0), slots), ([7; 3], [3; 3]));
+}
+
+
+#[derive(Injectable)]
+#[injectable(derive_default)]
+struct RetryPolicy {
+    #[inject(|| 3)]
+    attempts: u8,
+    #[inject(|| std::time::Duration::from_millis(250))]
+    backoff: std::time::Duration,
+    #[inject(default)]
+    jitter: bool,
+}
+
+
+#[derive(serde::Deserialize)]
+struct ServerConfig {
+    host: String,
+    port: u16,
+    #[allow(dead_code)]
+    debug: bool,
+}
+
+#[derive(Injectable)]
+#[injectable(from_config = "ServerConfig")]
+struct HttpServer {
+    host: String,
+    port: u16,
+    #[inject(|| 3)]
+    retries: u8,
+}
+
+
+#[test]
+fn it_injects_deserialized_config() {
+    let config: ServerConfig = serde_json::from_str(r#"{ "host": "localhost", "port": 8080, "debug": true }"#)
+        .expect("valid config");
+
+    let server = Container::new().construct::<HttpServer>(config);
+
+    assert_eq!((server.host.as_str(), server.port, server.retries), ("localhost", 8080, 3));
+}
+
+
+#[derive(Injectable)]
+#[injectable(self_resolve)]
+struct Standalone {
+    inner: Inner,
+    #[inject(|| 4)]
+    retries: u8,
+}
+
+
+#[test]
+fn it_resolves_itself_from_a_fresh_container() {
+    let standalone = Standalone::resolve();
+    let resolved = Container::new().resolve::<Standalone>();
+
+    assert_eq!((standalone.inner.0, standalone.retries), (resolved.inner.0, resolved.retries));
+    assert_eq!(standalone.retries, 4);
+}
+
+
+#[test]
+fn it_derives_default_from_factories() {
+    let policy = RetryPolicy::default();
+    let resolved = Container::new().resolve::<RetryPolicy>();
+
+    assert_eq!((policy.attempts, policy.backoff, policy.jitter), (3, std::time::Duration::from_millis(250), false));
+    assert_eq!((resolved.attempts, resolved.backoff), (policy.attempts, policy.backoff));
+}
+
+
+#[cfg(feature = "std")]
+#[derive(Injectable)]
+struct DatabaseConfig {
+    #[inject(env = "SINGULARITY_TEST_DATABASE_URL")]
+    url: String,
+    #[inject(env = "SINGULARITY_TEST_POOL_SIZE")]
+    pool_size: u8,
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+fn it_reads_fields_from_the_environment() {
+    // SAFETY: no other test touches these variables
+    unsafe {
+        std::env::set_var("SINGULARITY_TEST_DATABASE_URL", "postgres://localhost");
+        std::env::set_var("SINGULARITY_TEST_POOL_SIZE", "12");
+    }
+
+    let config = Container::new().resolve::<DatabaseConfig>();
+    assert_eq!((config.url.as_str(), config.pool_size), ("postgres://localhost", 12));
+}
+
+
+#[cfg(feature = "std")]
+#[derive(Injectable)]
+struct ListenConfig {
+    #[inject(env = "SINGULARITY_TEST_UNSET_PORT")]
+    #[allow(dead_code)]
+    port: u16,
+}
+
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic(expected = "environment variable `SINGULARITY_TEST_UNSET_PORT` is not set")]
+fn it_names_an_unset_environment_variable() {
+    Container::new().resolve::<ListenConfig>();
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Uuid(u128);
+
+impl Injectable for Uuid {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(0x5eed)
+    }
+}
+
+#[derive(Injectable)]
+#[injectable(transparent)]
+struct UserId(Uuid);
+
+
+#[test]
+fn it_derives_transparent_newtypes() {
+    let UserId(id) = Container::new().resolve::<UserId>();
+
+    assert_eq!(id, Uuid(0x5eed));
+}
+
+
+#[derive(Injectable)]
+#[injectable(singleton)]
+union StatusRegister {
+    raw: u32,
+    #[inject(|| [1, 0, 0, 0])]
+    bytes: [u8; 4],
+}
+
+
+#[test]
+fn it_derives_unions_from_their_injected_field() {
+    let register = Container::new().resolve::<StatusRegister>();
+
+    // SAFETY: `bytes` is the field the derive initializes, and `u32` has no invalid bit patterns
+    let (bytes, raw) = unsafe { (register.bytes, register.raw) };
+    assert_eq!(bytes, [1, 0, 0, 0]);
+    assert_eq!(raw, u32::from_ne_bytes(bytes));
+}
+
+
+#[derive(Injectable)]
+struct Storage {
+    primary: Inner,
+    replica: Outer,
+}
+
+#[derive(Injectable)]
+struct Catalog {
+    #[inject(flatten)]
+    storage: Storage,
+    cache: Inner,
+}
+
+
+#[test]
+fn it_derives_flattened_fields_from_their_deps() {
+    fn deps_of<T: Injectable<Deps = D>, D>() {}
+    deps_of::<Catalog, ((Inner, Outer), Inner)>();
+
+    let built = Catalog::inject(((Inner(1), Outer(2)), Inner(3)));
+    assert_eq!((built.storage.primary.0, built.storage.replica.0, built.cache.0), (1, 2, 3));
+
+    let resolved = Container::new().resolve::<Catalog>();
+    assert_eq!((resolved.storage.primary.0, resolved.storage.replica.0, resolved.cache.0), (7, 9, 7));
+}
+
+
+#[derive(ResolveBundle)]
+struct AppServices {
+    inner: Inner,
+    outer: Outer,
+}
+
+
+#[test]
+fn it_resolves_a_bundle_of_services() {
+    let AppServices { inner, outer } = Container::new().resolve::<AppServices>();
+
+    assert_eq!((inner.0, outer.0), (7, 9));
+}
+
+
+trait Backend {
+    fn name(&self) -> &'static str;
+}
+
+impl Backend for Inner {
+    fn name(&self) -> &'static str {
+        "inner"
+    }
+}
+
+impl Backend for Outer {
+    fn name(&self) -> &'static str {
+        "outer"
+    }
+}
+
+#[derive(Injectable)]
+struct BackedRepo<T: Backend = Inner> {
+    backend: T,
+}
+
+
+#[test]
+fn it_resolves_with_a_default_type_parameter() {
+    let container = Container::new();
+
+    assert_eq!(container.resolve::<BackedRepo>().backend.name(), "inner");
+    assert_eq!(container.resolve::<BackedRepo<Outer>>().backend.name(), "outer");
+}
+
+
+thread_local! {
+    static SUMMARIES: std::cell::Cell<u32> = const { std::cell::Cell::new(0) };
+}
+
+fn summarize() -> String {
+    SUMMARIES.with(|runs| runs.set(runs.get() + 1));
+    "7 rows".into()
+}
+
+#[derive(Injectable)]
+struct Report {
+    inner: Inner,
+    #[inject(once = summarize)]
+    summary: std::cell::OnceCell<String>,
+}
+
+
+#[test]
+fn it_computes_once_fields_on_first_access_only() {
+    let report = Container::new().resolve::<Report>();
+    assert_eq!(SUMMARIES.with(std::cell::Cell::get), 0);
+
+    assert_eq!(report.summary(), "7 rows");
+    assert_eq!(report.summary(), "7 rows");
+    assert_eq!((SUMMARIES.with(std::cell::Cell::get), report.inner.0), (1, 7));
+}
+
+
+#[derive(Clone)]
+struct DbHandle(&'static str);
+
+/// Registration keys, one per database
+#[derive(Clone)]
+struct PrimaryDb(DbHandle);
+#[derive(Clone)]
+struct ReplicaDb(DbHandle);
+
+impl From<PrimaryDb> for DbHandle {
+    fn from(key: PrimaryDb) -> Self {
+        key.0
+    }
+}
+
+impl From<ReplicaDb> for DbHandle {
+    fn from(key: ReplicaDb) -> Self {
+        key.0
+    }
+}
+
+#[derive(Injectable)]
+struct Replicated {
+    #[inject(as = "PrimaryDb")]
+    primary: DbHandle,
+    #[inject(as = "ReplicaDb")]
+    replica: DbHandle,
+}
+
+
+#[test]
+fn it_disambiguates_same_typed_fields_by_alias() {
+    let container = Container::new();
+    container.register_instance(PrimaryDb(DbHandle("db-1")));
+    container.register_instance(ReplicaDb(DbHandle("db-2")));
+
+    let replicated = container.resolve::<Replicated>();
+
+    assert_eq!((replicated.primary.0, replicated.replica.0), ("db-1", "db-2"));
+}
+
+
+#[derive(Injectable)]
+struct Queue<T: Default + Clone> {
+    inner: Inner,
+    #[inject(|| Vec::<T>::with_capacity(4))]
+    items: Vec<T>,
+    #[inject(|| vec![T::default(); 2])]
+    seeded: Vec<T>,
+}
+
+
+#[test]
+fn it_lets_factories_use_type_parameters() {
+    let queue = Container::new().resolve::<Queue<u8>>();
+
+    assert!(queue.items.is_empty() && queue.items.capacity() >= 4);
+    assert_eq!((queue.seeded, queue.inner.0), (vec![0, 0], 7));
+}
+
+
+#[derive(Injectable)]
+enum Transport {
+    Tcp(Inner),
+    Unix(Outer),
+}
+
+
+#[test]
+fn it_resolves_the_selected_enum_variant() {
+    let resolve_as = |variant| {
+        let container = Container::new();
+        container.register_instance(VariantSelector::<Transport>::new(variant));
+        container.resolve::<Transport>()
+    };
+
+    assert!(matches!(resolve_as("Tcp"), Transport::Tcp(Inner(7))));
+    assert!(matches!(resolve_as("Unix"), Transport::Unix(Outer(9))));
+}
+
+
+#[derive(Injectable)]
+enum Wire {
+    Plain(Inner),
+    Framed(Outer),
+}
+
+
+#[test]
+fn it_selects_a_variant_per_enum_type() {
+    let container = Container::new();
+    container.register_instance(VariantSelector::<Transport>::new("Tcp"));
+    container.register_instance(VariantSelector::<Wire>::new("Framed"));
+
+    assert!(matches!(container.resolve::<Transport>(), Transport::Tcp(Inner(7))));
+    assert!(matches!(container.resolve::<Wire>(), Wire::Framed(Outer(9))));
+
+    container.register_instance(VariantSelector::<Wire>::new("Plain"));
+    assert!(matches!(container.resolve::<Wire>(), Wire::Plain(Inner(7))));
+    assert!(matches!(container.resolve::<Transport>(), Transport::Tcp(_)));
+}
+
+
+#[test]
+#[should_panic(expected = "no variant `Pipe` on `Transport`, expected one of `Tcp`, `Unix`")]
+fn it_rejects_an_unknown_enum_variant() {
+    let container = Container::new();
+    container.register_instance(VariantSelector::<Transport>::new("Pipe"));
+
+    container.resolve::<Transport>();
+}
+
+
+#[derive(Injectable)]
+struct Endpoint {
+    #[inject("localhost")]
+    host: String,
+    #[inject(8080)]
+    port: u16,
+    #[inject(true)]
+    tls: bool,
+}
+
+#[derive(Injectable)]
+struct Probe(Inner, #[inject("/health")] &'static str, #[inject(3)] u8, #[inject(false)] bool);
+
+
+#[test]
+fn it_injects_literals_directly() {
+    let container = Container::new();
+
+    let endpoint = container.resolve::<Endpoint>();
+    assert_eq!((endpoint.host.as_str(), endpoint.port, endpoint.tls), ("localhost", 8080, true));
+
+    let Probe(inner, path, retries, verbose) = container.resolve::<Probe>();
+    assert_eq!((inner.0, path, retries, verbose), (7, "/health", 3, false));
+}
+
+
+#[derive(Injectable)]
+#[injectable(clone, singleton)]
+struct Snapshot {
+    #[inject(|| vec![1, 2])]
+    rows: Vec<u8>,
+    #[inject("v1")]
+    label: String,
+}
+
+
+#[test]
+fn it_derives_clone_through_the_option() {
+    let cached = Container::new().resolve_ref::<Snapshot>();
+
+    let snapshot = Snapshot::clone(&cached);
+
+    assert_eq!((snapshot.rows, snapshot.label), (vec![1, 2], "v1".to_string()));
+}
+
+
+#[derive(Injectable)]
+struct Gateway {
+    inner: Inner,
+    outer: Outer,
+    transport: Transport,
+    #[inject(|| 30)]
+    timeout_secs: u64,
+}
+
+
+#[test]
+fn it_exposes_the_dependency_count_as_a_const() {
+    const FAN_OUT: usize = <Gateway as Injectable>::DEP_COUNT;
+
+    assert_eq!(FAN_OUT, 3);
+    assert_eq!(<Endpoint as Injectable>::DEP_COUNT, 0);
+    assert_eq!(<Queue<u8> as Injectable>::DEP_COUNT, 1);
+    assert_eq!(<Transport as Injectable>::DEP_COUNT, 3);
+
+    let container = Container::new();
+    container.register_instance(VariantSelector::<Transport>::new("Unix"));
+    let gateway = container.resolve::<Gateway>();
+    assert!(matches!(gateway.transport, Transport::Unix(_)));
+    assert_eq!((gateway.inner.0, gateway.outer.0, gateway.timeout_secs), (7, 9, 30));
+}
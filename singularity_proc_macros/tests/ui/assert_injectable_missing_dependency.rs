@@ -0,0 +1,13 @@
+use singularity::assert_injectable;
+use singularity::container::*;
+
+struct Pool;
+
+#[derive(Injectable)]
+struct Repository {
+    pool: Pool,
+}
+
+assert_injectable!(Repository);
+
+fn main() {}
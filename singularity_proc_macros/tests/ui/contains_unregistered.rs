@@ -0,0 +1,21 @@
+use singularity::container::*;
+
+#[derive(Injectable)]
+struct Inner;
+
+/// Never made `Injectable`, nor registered
+struct Unregistered;
+
+#[derive(Injectable)]
+struct Service {
+    inner: Inner,
+    unregistered: Unregistered,
+}
+
+fn main() {
+    let container = Container::new();
+
+    assert!(container.contains::<Inner>());
+    container.contains::<Unregistered>();
+    container.contains::<Service>();
+}
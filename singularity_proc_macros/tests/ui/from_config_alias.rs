@@ -0,0 +1,26 @@
+use singularity::container::*;
+
+#[derive(Clone)]
+struct ServerConfig {
+    port: u16,
+}
+
+impl Injectable for ServerConfig {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self { port: 8080 }
+    }
+}
+
+#[derive(Clone)]
+struct AdminPort(u16);
+
+#[derive(Injectable)]
+#[injectable(from_config = "ServerConfig")]
+struct Server {
+    #[inject(as = "AdminPort")]
+    port: u16,
+}
+
+fn main() {}
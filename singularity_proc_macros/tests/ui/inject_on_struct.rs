@@ -0,0 +1,7 @@
+use singularity::container::*;
+
+#[derive(Injectable)]
+#[inject(|| 1)]
+struct Counter(i32);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use singularity::container::*;
+use singularity::injectable;
+
+injectable!(() => Dep);
+
+injectable!((self: Dep) => Service { count: u8 = 1 });
+
+fn main() {}
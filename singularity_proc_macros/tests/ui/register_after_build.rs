@@ -0,0 +1,7 @@
+use singularity::container::*;
+
+fn main() {
+    let container = Container::builder().instance(8080_u16).build();
+
+    container.register_instance(String::from("localhost"));
+}
@@ -0,0 +1,13 @@
+use singularity::container::*;
+
+#[derive(Injectable)]
+struct Database;
+
+#[derive(ResolveBundle)]
+struct AppServices {
+    db: Database,
+    #[inject(|| 3)]
+    retries: u8,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use singularity::container::*;
+
+#[derive(Injectable)]
+struct Inner;
+
+struct Endpoint(&'static str);
+
+#[derive(Injectable)]
+#[injectable(strict)]
+struct Service {
+    inner: Inner,
+    endpoint: Endpoint,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use singularity::container::*;
+
+struct Leaf;
+
+impl Injectable for Leaf {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self
+    }
+}
+
+#[derive(Injectable)]
+struct Kitchen(
+    Leaf, Leaf, Leaf, Leaf, Leaf, Leaf, Leaf, Leaf, Leaf,
+    Leaf, Leaf, Leaf, Leaf, Leaf, Leaf, Leaf, Leaf,
+);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use singularity::container::*;
+
+#[derive(Injectable)]
+struct A;
+
+#[derive(Injectable)]
+struct B;
+
+fn main() {
+    let _ = Container::new().resolve::<(A, B)>();
+}
@@ -0,0 +1,9 @@
+use singularity::container::*;
+
+#[derive(Injectable)]
+union Word {
+    raw: u32,
+    bytes: [u8; 4],
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use singularity::container::*;
+
+#[derive(Injectable)]
+struct Counter(#[inject(|x: i32| x + 1)] i32);
+
+fn main() {}
@@ -1,18 +1,110 @@
 ﻿
-mod injectable;
+/// `into_inner`, `Deref` and `DerefMut` for a dependency wrapper `$name(pub $inner)`.
+///
+/// Declared ahead of the modules, `macro_rules!` are only in scope below their definition.
+macro_rules! dependency_wrapper {
+    ($name:ident<$T:ident $(: ?$unsized:ident)?>, $inner:ty => $target:ty, $doc:literal) => {
+        impl<$T $(: ?$unsized)?> $name<$T> {
+            #[doc = $doc]
+            #[inline(always)]
+            pub fn into_inner(self) -> $inner {
+                self.0
+            }
+        }
+
+        impl<$T $(: ?$unsized)?> ::std::ops::Deref for $name<$T> {
+            type Target = $target;
+
+            #[inline(always)]
+            fn deref(&self) -> &$target {
+                &self.0
+            }
+        }
 
+        impl<$T $(: ?$unsized)?> ::std::ops::DerefMut for $name<$T> {
+            #[inline(always)]
+            fn deref_mut(&mut self) -> &mut $target {
+                &mut self.0
+            }
+        }
+    };
+}
+
+mod async_injectable;
+mod bound;
+mod bundle;
+mod builder;
+mod cache;
+mod cloned;
+#[cfg(feature = "default-fallback")]
+mod defaulted;
+mod disposable;
+mod injectable;
 mod invokable;
+mod lazy_dep;
+mod post_construct;
+mod registered;
 mod resolve_deps_from;
+mod resolve_error;
 mod resolver;
 mod scope;
+mod sealed_container;
+#[cfg(feature = "std-defaults")]
+mod std_defaults;
+mod sync_container;
+#[cfg(feature = "trace")]
+mod trace;
+mod try_injectable;
+mod variant_selector;
 
+pub use async_injectable::{AsyncInjectable, ResolveTimeout};
+pub use bound::Bound;
+pub use bundle::Bundle;
+#[cfg(feature = "derive")]
+pub use bundle::ResolveBundle;
+pub use builder::ContainerBuilder;
+pub use cloned::Cloned;
+#[cfg(feature = "default-fallback")]
+pub use defaulted::Defaulted;
+pub use disposable::Disposable;
 pub use injectable::Injectable;
+pub use invokable::Invokable;
+pub use lazy_dep::LazyDep;
+pub use post_construct::PostConstruct;
+pub use registered::{Preferred, Registered};
+pub use resolve_deps_from::DepsTuple;
+pub use resolve_error::ResolveError;
+pub use resolver::Resolver;
+pub use scope::{DeclaredScope, ParseScopeError, Scope, ScopePolicy};
+pub use sealed_container::SealedContainer;
+pub use sync_container::SyncContainer;
+pub use try_injectable::TryInjectable;
+pub use variant_selector::VariantSelector;
 
-// pub use invokable::Invokable;
+use cache::{get_or_build, Cached};
+pub(crate) use resolve_deps_from::ResolveDepsFrom;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::hash::Hash;
+use std::pin::pin;
+use std::rc::Rc;
+use std::task::Poll;
+use std::time::Duration;
 
-use resolve_deps_from::ResolveDepsFrom;
 pub mod macros {
     pub use super::injectable::injectable as injectable;
+    pub use super::registered::register as register;
+}
+
+/// The `#[injectable]` attribute, kept apart from the glob-importable items:
+/// in scope, it would clash with the derive's `#[injectable(...)]` helper.
+#[cfg(feature = "derive")]
+pub mod attribute {
+    pub use singularity_proc_macros::injectable;
 }
 
 
@@ -31,18 +123,176 @@ pub mod macros {
 /// - **Constructor-based dependency flow**
 /// - **Circular dependencies caught at compile time**
 /// - Supports up to **8 dependency parameters**
-
-
+///
+/// Types that can't implement `Injectable` (orphan rule) can be registered
+/// at runtime via [`Container::register_factory`] and depended on through
+/// [`Registered`].
 pub struct Container {
-    /// Prevents direct struct initialization via `Container {}` or `Container;`
-    /// Enforces usage via `Container::new()`
-    _private: (),
+    /// Runtime factories keyed by the produced type.
+    /// Private, so construction goes through `Container::new()`.
+    /// Registries are `Rc`-shared with every child scope.
+    factories: Shared<HashMap<TypeId, Factory>>,
+    /// Shared instances handed out by `resolve_ref`.
+    singletons: Shared<HashMap<TypeId, Cached>>,
+    /// Singleton keys in construction order, so `Drop` can tear down in reverse.
+    construction_order: Shared<Vec<TypeId>>,
+    /// Instances swapped out by `replace_singleton`, kept for outstanding `borrow`s.
+    retired: Shared<Vec<Rc<dyn Any>>>,
+    /// `Copy` singletons stored by value, handed out by `resolve_copy`.
+    copies: Shared<HashMap<TypeId, Box<dyn Any>>>,
+    /// Keyed implementor constructors, one `Vec<(K, Keyed<Tr>)>` per `(K, Tr)` pair.
+    keyed: Shared<HashMap<TypeId, Box<dyn Any>>>,
+    /// Plugin constructors, one `Vec<Plugin<P>>` per plugin type `P`.
+    plugins: Shared<HashMap<TypeId, Box<dyn Any>>>,
+    /// Named factories, several per produced type.
+    named: Shared<HashMap<TypeId, HashMap<String, Factory>>>,
+    /// Trait bindings keyed by the `dyn Trait`, each producing a `Box<Tr>`.
+    bindings: Shared<HashMap<TypeId, Factory>>,
+    /// `Disposable` types, each with its type-erased `dispose`.
+    disposers: Shared<HashMap<TypeId, Disposer>>,
+    /// Runtime scope overrides, take precedence over `T::SCOPE`.
+    scopes: Shared<HashMap<TypeId, Scope>>,
+    /// Has the final say over every type's scope.
+    policy: Rc<dyn ScopePolicy>,
+    /// Consulted for registrations this container doesn't have, see `with_parent`.
+    parent: Option<Rc<Container>>,
+    /// Searched before the registries once sealed, see `seal`.
+    sealed: Option<Rc<sealed_container::Tables>>,
+    /// Names of every type registered or scoped at runtime, for `Debug`.
+    type_names: Shared<HashMap<TypeId, &'static str>>,
+    /// Instances shared for the duration of the running `resolve_deduped`, if any.
+    per_call: RefCell<Option<HashMap<TypeId, Rc<dyn Any>>>>,
+    /// Per-scope instances of `Scope::Scoped` types, `None` on the root.
+    scoped: Option<Shared<HashMap<TypeId, Rc<dyn Any>>>>,
+    /// Accumulated `inject` time per type name.
+    #[cfg(feature = "profiling")]
+    timings: Shared<HashMap<&'static str, std::time::Duration>>,
+    /// Number of `resolve` calls per type name.
+    #[cfg(feature = "metrics")]
+    resolution_counts: Shared<HashMap<&'static str, u64>>,
 }
 
+/// State shared between a container and its child scopes.
+type Shared<T> = Rc<RefCell<T>>;
+
+/// Type-erased runtime factory; `Rc` so it can be called without holding the borrow.
+type Factory = Rc<dyn Fn(&Container) -> Box<dyn Any>>;
+
+/// Constructor of one keyed implementor of `Tr`.
+type Keyed<Tr> = Rc<dyn Fn(&Container) -> Box<Tr>>;
+
+/// Constructor of one registered plugin.
+type Plugin<P> = Rc<dyn Fn(&Container) -> P>;
+
+/// Disposes a cached instance of the type it was registered for.
+type Disposer = fn(&mut dyn Any);
+
+/// Cache key of the plugin slice for `P`, so it can't collide with a `Vec<P>` singleton.
+struct PluginSlice<P>(std::marker::PhantomData<P>);
+
 impl Container {
 
     pub fn new() -> Self {
-        Container { _private: () }
+        Self::with_capacity(0)
+    }
+
+    /// Preallocates the singleton cache for `capacity` types,
+    /// mirroring `HashMap::with_capacity`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Container {
+            factories: Rc::default(),
+            singletons: Rc::new(RefCell::new(HashMap::with_capacity(capacity))),
+            construction_order: Rc::default(),
+            retired: Rc::default(),
+            copies: Rc::default(),
+            keyed: Rc::default(),
+            plugins: Rc::default(),
+            named: Rc::default(),
+            bindings: Rc::default(),
+            disposers: Rc::default(),
+            scopes: Rc::default(),
+            policy: Rc::new(DeclaredScope),
+            parent: None,
+            sealed: None,
+            type_names: Rc::default(),
+            per_call: RefCell::default(),
+            scoped: None,
+            #[cfg(feature = "profiling")]
+            timings: Rc::default(),
+            #[cfg(feature = "metrics")]
+            resolution_counts: Rc::default(),
+        }
+    }
+
+    /// Opens a child scope.
+    ///
+    /// The child shares every registration and singleton with `self`, but
+    /// caches `Scope::Scoped` instances on its own, so siblings never see
+    /// each other's. Registrations made through a child are visible to all.
+    pub fn child(&self) -> Self {
+        let mut child = self.handle();
+        child.scoped = Some(Rc::default());
+        child
+    }
+
+    /// Another handle onto this very container: every registry and cache,
+    /// this scope's included, is shared.
+    pub(crate) fn handle(&self) -> Self {
+        Container {
+            factories: self.factories.clone(),
+            singletons: self.singletons.clone(),
+            construction_order: self.construction_order.clone(),
+            retired: self.retired.clone(),
+            copies: self.copies.clone(),
+            keyed: self.keyed.clone(),
+            plugins: self.plugins.clone(),
+            named: self.named.clone(),
+            bindings: self.bindings.clone(),
+            disposers: self.disposers.clone(),
+            scopes: self.scopes.clone(),
+            policy: self.policy.clone(),
+            parent: self.parent.clone(),
+            sealed: self.sealed.clone(),
+            type_names: self.type_names.clone(),
+            per_call: RefCell::default(),
+            scoped: self.scoped.clone(),
+            #[cfg(feature = "profiling")]
+            timings: self.timings.clone(),
+            #[cfg(feature = "metrics")]
+            resolution_counts: self.resolution_counts.clone(),
+        }
+    }
+
+    /// A container whose scopes are all decided by `policy`, e.g.
+    /// `Container::with_policy(Scope::Transient)` to share nothing in tests.
+    pub fn with_policy(policy: impl ScopePolicy + 'static) -> Self {
+        let mut container = Self::new();
+        container.policy = Rc::new(policy);
+        container
+    }
+
+    /// A container of its own that falls back to `parent` for runtime
+    /// registrations (factories, named factories, bindings) it lacks.
+    ///
+    /// Unlike [`Container::child`] nothing is shared: registrations and caches
+    /// start empty and stay local. A fallback runs on the parent, so whatever
+    /// the parent's factory resolves comes from the parent too. `Injectable`
+    /// types need no fallback, every container builds them the same way.
+    pub fn with_parent(parent: Rc<Container>) -> Self {
+        let mut container = Self::new();
+        container.parent = Some(parent);
+        container
+    }
+
+    /// Freezes the registrations once startup is done: the result can only
+    /// resolve, see [`SealedContainer`].
+    pub fn seal(self) -> SealedContainer {
+        SealedContainer::new(self)
+    }
+
+    /// Starts a [`ContainerBuilder`] to register everything up front.
+    pub fn builder() -> ContainerBuilder {
+        ContainerBuilder::new()
     }
 
     #[inline(always)]
@@ -51,27 +301,932 @@ impl Container {
         T: Injectable,
         T::Deps: ResolveDepsFrom<Self>,
     {
-        T::inject(T::Deps::resolve_deps(self))
-    }
-
-    // pub fn invoke<T>(&self)
-    // where
-    //     T: Invokable,
-    //     <T as Invokable>::Deps: ResolveDepsFrom<Self>,
-    // {
-    //     let deps = <T as Invokable>::Deps::resolve_deps(self);
-    //     T::invoke(deps);
-    // }
-    // 
-    // 
-    // pub fn invoke_with<T>(&self, callback: impl FnOnce(T::Output))
-    // where
-    //     T: Invokable,
-    //     <T as Invokable>::Deps: ResolveDepsFrom<Self>,
-    // {
-    //     let deps = <T as Invokable>::Deps::resolve_deps(self);
-    //     T::invoke_with(deps, callback);
-    // }
+        let Ok(instance) = self.build::<T, _, _, Infallible>(|| Ok(T::Deps::resolve_deps(self)), |deps| Ok(T::inject(deps)));
+        instance
+    }
+
+    /// Resolves `T`, reporting a missing registration (a `Registered<_>` or
+    /// `Bound<_>` dep with nothing behind it) as an error instead of a panic.
+    /// With `panic-on-error`, a `TryInjectable` dep that fails to construct
+    /// comes back as [`ResolveError::ConstructionFailed`].
+    ///
+    /// Nothing is caught: a panicking `inject` or factory still unwinds.
+    pub fn resolve_checked<T>(&self) -> Result<T, ResolveError>
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        self.build::<T, _, _, _>(|| T::Deps::try_resolve_deps(self), T::inject_checked)
+    }
+
+    /// Injects `T` with whatever `deps` resolves, traced, counted and timed
+    /// the same way whichever of `resolve`, `resolve_checked`, `try_resolve`,
+    /// `resolve_async` or `invoke` asked. `inject` makes what is handed back,
+    /// usually `T` itself.
+    // `T` only names the entries, unused with every instrumentation feature off
+    #[cfg_attr(
+        not(any(feature = "trace", feature = "metrics", feature = "profiling")),
+        allow(clippy::extra_unused_type_parameters)
+    )]
+    #[inline(always)]
+    fn build<T, D, V, E>(&self, deps: impl FnOnce() -> Result<D, E>, inject: impl FnOnce(D) -> Result<V, E>) -> Result<V, E> {
+        // Held until `T` is built, so deps resolved meanwhile log one level deeper
+        #[cfg(feature = "trace")]
+        let _depth = trace::enter::<T>();
+
+        #[cfg(feature = "metrics")]
+        {
+            *self.resolution_counts.borrow_mut().entry(std::any::type_name::<T>()).or_default() += 1;
+        }
+
+        #[cfg(not(feature = "profiling"))]
+        {
+            deps().and_then(inject)
+        }
+
+        // Only `inject` itself is timed, deps record their own entries
+        #[cfg(feature = "profiling")]
+        {
+            let deps = deps()?;
+            let started = std::time::Instant::now();
+            let instance = inject(deps);
+            self.record_timing::<T>(started.elapsed());
+            instance
+        }
+    }
+
+    /// Adds `took` to `T`'s entry in [`Container::timings`].
+    #[cfg(feature = "profiling")]
+    fn record_timing<T>(&self, took: std::time::Duration) {
+        *self.timings.borrow_mut().entry(std::any::type_name::<T>()).or_default() += took;
+    }
+
+    /// Resolves `T`, handing back the payload of any panic on the way (an
+    /// `inject`, a missing registration, ...) instead of unwinding further.
+    ///
+    /// A safety net for graphs composed at runtime. Caches stay consistent,
+    /// but whatever the failed resolution built before panicking is dropped.
+    #[cfg(feature = "std")]
+    pub fn catch_resolve<T>(&self) -> Result<T, Box<dyn Any + Send>>
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.resolve::<T>()))
+    }
+
+    /// Resolves `T`, building each `Rc<_>` dep at most once for the whole call.
+    ///
+    /// Where a graph reaches the same transient (or unscoped `Scoped`) type
+    /// along several branches, every branch gets the one instance; nothing
+    /// outlives the call, the next one builds afresh. Only shared handles can
+    /// be deduplicated: `Rc<Node>` deps (anything going through
+    /// [`Container::resolve_ref`]), plain `Node` deps are still built per use.
+    pub fn resolve_deduped<T>(&self) -> T
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        // A nested call joins the running one
+        if cfg!(feature = "static-container") || self.per_call.borrow().is_some() {
+            return self.resolve::<T>();
+        }
+
+        /// Closes the call on the way out, unwinding included.
+        struct CallGuard<'c>(&'c RefCell<Option<HashMap<TypeId, Rc<dyn Any>>>>);
+
+        impl Drop for CallGuard<'_> {
+            fn drop(&mut self) {
+                self.0.take();
+            }
+        }
+
+        self.per_call.replace(Some(HashMap::new()));
+        let _guard = CallGuard(&self.per_call);
+        self.resolve::<T>()
+    }
+
+    /// A fresh `Rc<T>` from `build`, or the one already built during the
+    /// running [`Container::resolve_deduped`].
+    fn resolve_per_call<T: 'static, E>(&self, build: impl FnOnce() -> Result<T, E>) -> Result<Rc<T>, E> {
+        if self.per_call.borrow().is_none() {
+            return build().map(Rc::new);
+        }
+
+        let key = TypeId::of::<T>();
+        let instance = get_or_build(
+            || self.per_call.borrow().as_ref().and_then(|instances| instances.get(&key).cloned()),
+            || build().map(|instance| Rc::new(instance) as Rc<dyn Any>),
+            |instance| {
+                if let Some(instances) = self.per_call.borrow_mut().as_mut() {
+                    instances.insert(key, instance.clone());
+                }
+                instance
+            },
+        )?;
+        Ok(instance.downcast::<T>().expect("per-call cache type mismatch"))
+    }
+
+    /// Resolves `T`, then runs its [`PostConstruct`] hook.
+    pub fn resolve_init<T>(&self) -> T
+    where
+        T: PostConstruct,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        let mut instance = self.resolve::<T>();
+        instance.post_construct(self);
+        instance
+    }
+
+    /// Builds `T` from deps you already have, skipping their resolution.
+    #[inline(always)]
+    pub fn construct<T: Injectable>(&self, deps: T::Deps) -> T {
+        T::inject(deps)
+    }
+
+    /// Resolves `T`'s deps, then awaits its construction.
+    pub async fn resolve_async<T>(&self) -> T
+    where
+        T: AsyncInjectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        // `build` only makes the future, the awaited construction is timed here
+        let Ok(construction) = self.build::<T, _, _, Infallible>(|| Ok(T::Deps::resolve_deps(self)), |deps| Ok(T::inject_async(deps)));
+        #[cfg(feature = "profiling")]
+        let started = std::time::Instant::now();
+        let instance = construction.await;
+        #[cfg(feature = "profiling")]
+        self.record_timing::<T>(started.elapsed());
+        instance
+    }
+
+    /// [`resolve_async`](Self::resolve_async), given up once `sleep(timeout)` completes.
+    ///
+    /// Bring the runtime's timer, e.g. `tokio::time::sleep`; nothing here
+    /// depends on one. Deps resolve synchronously, so only the awaited
+    /// construction can be cut short.
+    pub async fn resolve_async_timeout<T, S>(
+        &self,
+        timeout: Duration,
+        sleep: impl FnOnce(Duration) -> S,
+    ) -> Result<T, ResolveTimeout>
+    where
+        T: AsyncInjectable,
+        T::Deps: ResolveDepsFrom<Self>,
+        S: Future<Output = ()>,
+    {
+        let mut construction = pin!(self.resolve_async::<T>());
+        let mut timer = pin!(sleep(timeout));
+
+        // Construction is polled first, so a service ready along with the timer wins
+        poll_fn(|cx| match construction.as_mut().poll(cx) {
+            Poll::Ready(service) => Poll::Ready(Ok(service)),
+            Poll::Pending => timer.as_mut().poll(cx).map(|()| Err(ResolveTimeout::new::<T>(timeout))),
+        })
+        .await
+    }
+
+    /// Resolves a fallibly constructed `T`, handing back its error.
+    ///
+    /// Only `T` itself may fail, its deps resolve as usual.
+    pub fn try_resolve<T>(&self) -> Result<T, T::Error>
+    where
+        T: TryInjectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        self.build::<T, _, _, _>(|| Ok(T::Deps::resolve_deps(self)), T::try_inject)
+    }
+
+    /// Total time spent in each type's `inject` (or `try_inject`, awaited
+    /// `inject_async`, `invoke`), slowest first.
+    ///
+    /// Keyed by type name rather than `TypeId` so `resolve` needs no `'static` bound.
+    #[cfg(feature = "profiling")]
+    pub fn timings(&self) -> Vec<(&'static str, std::time::Duration)> {
+        let mut timings: Vec<_> = self.timings.borrow().iter().map(|(name, took)| (*name, *took)).collect();
+        timings.sort_by_key(|&(_, took)| std::cmp::Reverse(took));
+        timings
+    }
+
+    /// How many times each type has been built (by `resolve`, `try_resolve`,
+    /// `resolve_async`, ...) or invoked, cache hits excluded: a singleton
+    /// counted more than once is being rebuilt.
+    ///
+    /// Keyed by type name, like [`Container::timings`].
+    #[cfg(feature = "metrics")]
+    pub fn resolution_counts(&self) -> HashMap<&'static str, u64> {
+        self.resolution_counts.borrow().clone()
+    }
+
+    /// Resolves a shared handle to `T`, honoring `T::SCOPE`.
+    ///
+    /// - `Singleton` – built once, the container keeps it alive.
+    /// - `WeakSingleton` – built once per generation, rebuilt after every
+    ///   outside `Rc` has been dropped.
+    /// - `Scoped` – built once per [`Container::child`] scope, fresh on the root.
+    /// - `Transient` – built fresh on every call.
+    ///
+    /// A scope set via [`Container::set_scope`] overrides `T::SCOPE`, and the
+    /// container's [`ScopePolicy`] has the final say.
+    pub fn resolve_ref<T>(&self) -> Rc<T>
+    where
+        T: Injectable + 'static,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        let Ok(instance) = self.shared::<T, Infallible>(|| Ok(self.resolve::<T>()));
+        instance
+    }
+
+    /// [`Container::resolve_ref`] for an `Rc<T>` dep of `resolve_checked`.
+    pub(crate) fn resolve_ref_checked<T>(&self) -> Result<Rc<T>, ResolveError>
+    where
+        T: Injectable + 'static,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        self.shared(|| self.resolve_checked::<T>())
+    }
+
+    /// The `Rc<T>` `T`'s scope calls for, `build` making any new one.
+    fn shared<T, E>(&self, build: impl FnOnce() -> Result<T, E>) -> Result<Rc<T>, E>
+    where
+        T: Injectable + 'static,
+    {
+        let key = TypeId::of::<T>();
+        let scope = self.scope_of::<T>();
+
+        match scope {
+            Scope::Singleton | Scope::WeakSingleton => {
+                let instance = get_or_build(
+                    || self.singletons.borrow().get(&key).and_then(Cached::upgrade),
+                    || build().map(|instance| Rc::new(instance) as Rc<dyn Any>),
+                    |instance| {
+                        let entry = match scope {
+                            Scope::WeakSingleton => Cached::Weak(Rc::downgrade(&instance)),
+                            _ => Cached::Strong(instance.clone()),
+                        };
+                        self.singletons.borrow_mut().insert(key, entry);
+                        self.record_construction(key);
+                        instance
+                    },
+                )?;
+                Ok(instance.downcast::<T>().expect("singleton cache type mismatch"))
+            }
+            Scope::Scoped => self.scoped_or_per_call(build),
+            Scope::Transient => self.resolve_per_call(build),
+        }
+    }
+
+    /// Resolves `T` through this scope's cache: within one [`Container::child`]
+    /// every call shares an instance, on the root (or with the
+    /// `static-container` feature) each call builds fresh (unless a
+    /// [`Container::resolve_deduped`] is running).
+    pub fn resolve_scoped<T>(&self) -> Rc<T>
+    where
+        T: Injectable + 'static,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        let Ok(instance) = self.scoped_or_per_call::<T, Infallible>(|| Ok(self.resolve::<T>()));
+        instance
+    }
+
+    /// The scope cache behind [`Container::resolve_scoped`], `build` making any new instance.
+    fn scoped_or_per_call<T: 'static, E>(&self, build: impl FnOnce() -> Result<T, E>) -> Result<Rc<T>, E> {
+        let scoped = self.scoped.as_ref().filter(|_| !cfg!(feature = "static-container"));
+        let Some(scoped) = scoped else {
+            return self.resolve_per_call(build);
+        };
+
+        let key = TypeId::of::<T>();
+        let instance = get_or_build(
+            || scoped.borrow().get(&key).cloned(),
+            || build().map(|instance| Rc::new(instance) as Rc<dyn Any>),
+            |instance| {
+                scoped.borrow_mut().insert(key, instance.clone());
+                instance
+            },
+        )?;
+        Ok(instance.downcast::<T>().expect("scoped cache type mismatch"))
+    }
+
+    /// Resolves `n` instances of `T`, e.g. for a worker pool.
+    ///
+    /// Scope decides what "instance" means: `Transient` types are injected `n`
+    /// times independently, any other scope yields `n` clones of the one
+    /// instance [`Container::resolve_ref`] hands out (cached for singletons).
+    pub fn resolve_vec<T>(&self, n: usize) -> Vec<T>
+    where
+        T: Injectable + Clone + 'static,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        if self.scope_of::<T>() == Scope::Transient {
+            return (0..n).map(|_| self.resolve::<T>()).collect();
+        }
+
+        let shared = self.resolve_ref::<T>();
+        vec![T::clone(&shared); n]
+    }
+
+    /// A constructor for `T` to hand to code that shouldn't see the container:
+    /// every call is a fresh [`Container::resolve`], borrowing `self`.
+    pub fn resolver<T>(&self) -> impl Fn() -> T + '_
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        move || self.resolve::<T>()
+    }
+
+    /// Resolves a `Copy` service by value, honoring `T::SCOPE` without
+    /// handing out an `Rc`.
+    ///
+    /// Singletons (weak ones too, a copy can't be shared anyway) are built once
+    /// and kept in a by-value cache of their own, apart from `resolve_ref`'s:
+    /// one `Box<dyn Any>` each. A zero-sized singleton is the exception, see
+    /// [`Scope`]. Every other scope builds fresh.
+    pub fn resolve_copy<T>(&self) -> T
+    where
+        T: Injectable + Copy + 'static,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        let key = TypeId::of::<T>();
+        if !matches!(self.scope_of::<T>(), Scope::Singleton | Scope::WeakSingleton) {
+            return self.resolve::<T>();
+        }
+        if size_of::<T>() == 0 {
+            return *self.resolve_ref::<T>();
+        }
+
+        let Ok(value) = get_or_build::<_, Infallible>(
+            || {
+                let copies = self.copies.borrow();
+                copies.get(&key).map(|value| *value.downcast_ref::<T>().expect("copy cache type mismatch"))
+            },
+            || Ok(self.resolve::<T>()),
+            |value| {
+                self.copies.borrow_mut().insert(key, Box::new(value));
+                value
+            },
+        );
+        value
+    }
+
+    /// Registers an implementor of `Tr` (usually a `dyn Trait`) serving `key`.
+    ///
+    /// ```
+    /// # use singularity::container::*;
+    /// # #[derive(Clone, PartialEq, Eq, Hash)]
+    /// # enum Kind { Email }
+    /// # trait Handler {}
+    /// # singularity::injectable!(() => EmailHandler);
+    /// # impl Handler for EmailHandler {}
+    /// # let container = Container::new();
+    /// container.register_keyed::<Kind, dyn Handler>(Kind::Email, |c| Box::new(c.resolve::<EmailHandler>()));
+    /// # assert!(container.resolve_map::<Kind, dyn Handler>().contains_key(&Kind::Email));
+    /// ```
+    pub fn register_keyed<K, Tr>(&self, key: K, ctor: impl Fn(&Container) -> Box<Tr> + 'static)
+    where
+        K: 'static,
+        Tr: ?Sized + 'static,
+    {
+        let ctor: Keyed<Tr> = Rc::new(ctor);
+        self.keyed
+            .borrow_mut()
+            .entry(TypeId::of::<(K, Box<Tr>)>())
+            .or_insert_with(|| Box::new(Vec::<(K, Keyed<Tr>)>::new()))
+            .downcast_mut::<Vec<(K, Keyed<Tr>)>>()
+            .expect("keyed registry type mismatch")
+            .push((key, ctor));
+    }
+
+    /// Builds every implementor of `Tr` registered via `register_keyed`, keyed
+    /// by the discriminant it serves. Later registrations win on duplicate keys.
+    pub fn resolve_map<K, Tr>(&self) -> HashMap<K, Box<Tr>>
+    where
+        K: Hash + Eq + Clone + 'static,
+        Tr: ?Sized + 'static,
+    {
+        // Clone the constructors out so they may resolve through the container
+        let entries: Vec<(K, Keyed<Tr>)> = match self.keyed.borrow().get(&TypeId::of::<(K, Box<Tr>)>()) {
+            Some(entries) => entries
+                .downcast_ref::<Vec<(K, Keyed<Tr>)>>()
+                .expect("keyed registry type mismatch")
+                .clone(),
+            None => Vec::new(),
+        };
+
+        entries.into_iter().map(|(key, ctor)| (key, ctor(self))).collect()
+    }
+
+    /// Adds a plugin of type `P`, usually `Box<dyn Trait>`, after those already
+    /// registered; [`Container::resolve_slice`] builds them all.
+    ///
+    /// ```
+    /// # use singularity::container::*;
+    /// # trait Plugin {}
+    /// # singularity::injectable!(() => Metrics);
+    /// # impl Plugin for Metrics {}
+    /// # let container = Container::new();
+    /// container.register_plugin::<Box<dyn Plugin>>(|c| Box::new(c.resolve::<Metrics>()));
+    /// # assert_eq!(container.resolve_slice::<Box<dyn Plugin>>().len(), 1);
+    /// ```
+    pub fn register_plugin<P: 'static>(&self, ctor: impl Fn(&Container) -> P + 'static) {
+        let ctor: Plugin<P> = Rc::new(ctor);
+        self.plugins
+            .borrow_mut()
+            .entry(TypeId::of::<P>())
+            .or_insert_with(|| Box::new(Vec::<Plugin<P>>::new()))
+            .downcast_mut::<Vec<Plugin<P>>>()
+            .expect("plugin registry type mismatch")
+            .push(ctor);
+    }
+
+    /// Borrows every plugin registered for `P`, in registration order.
+    ///
+    /// They're built on first call into a container-owned `Vec<P>`, cached like
+    /// [`Container::borrow`] caches a singleton; plugins registered after that
+    /// aren't picked up. A `Deps` list can't borrow from the container, so a
+    /// service holding the slice is built with [`Container::construct`]:
+    ///
+    /// ```
+    /// # use singularity::container::*;
+    /// # trait Plugin {}
+    /// # singularity::injectable!(() => Metrics);
+    /// # impl Plugin for Metrics {}
+    /// struct Host<'c> {
+    ///     plugins: &'c [Box<dyn Plugin>],
+    /// }
+    ///
+    /// impl<'c> Injectable for Host<'c> {
+    ///     type Deps = &'c [Box<dyn Plugin>];
+    ///
+    ///     fn inject(plugins: Self::Deps) -> Self {
+    ///         Self { plugins }
+    ///     }
+    /// }
+    ///
+    /// # let container = Container::new();
+    /// # container.register_plugin::<Box<dyn Plugin>>(|c| Box::new(c.resolve::<Metrics>()));
+    /// let host = container.construct::<Host>(container.resolve_slice::<Box<dyn Plugin>>());
+    /// assert_eq!(host.plugins.len(), 1);
+    /// ```
+    pub fn resolve_slice<P: 'static>(&self) -> &[P] {
+        let key = TypeId::of::<PluginSlice<P>>();
+
+        let Ok(plugins) = get_or_build::<_, Infallible>(
+            || self.singletons.borrow().get(&key).and_then(Cached::upgrade),
+            || {
+                let ctors: Vec<Plugin<P>> = match self.plugins.borrow().get(&TypeId::of::<P>()) {
+                    Some(ctors) => ctors
+                        .downcast_ref::<Vec<Plugin<P>>>()
+                        .expect("plugin registry type mismatch")
+                        .clone(),
+                    None => Vec::new(),
+                };
+                Ok(Rc::new(ctors.iter().map(|ctor| ctor(self)).collect::<Vec<P>>()) as Rc<dyn Any>)
+            },
+            |plugins| {
+                self.record_construction(key);
+                plugins
+            },
+        );
+        self.pin::<Vec<P>>(key, plugins).as_slice()
+    }
+
+    /// Borrows the cached instance of `T`, building it on first use.
+    ///
+    /// Avoids `Rc` traffic for the single-threaded case: the reference lives as
+    /// long as the `&self` borrow. Borrowing pins the instance strongly in the
+    /// singleton cache, whatever `T`'s scope says, so it can't expire under you.
+    ///
+    /// Aliasing: only shared access is ever handed out (`&T` here, `Rc<T>` from
+    /// `resolve_ref`), and cache entries are never dropped while the container
+    /// lives, so the reference stays valid even if the entry is later replaced
+    /// (see [`Container::replace_singleton`]).
+    #[allow(clippy::should_implement_trait)] // generic over `T`, not `Borrow`
+    pub fn borrow<T>(&self) -> &T
+    where
+        T: Injectable + 'static,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        let key = TypeId::of::<T>();
+
+        let Ok(instance) = get_or_build::<_, Infallible>(
+            || self.singletons.borrow().get(&key).and_then(Cached::upgrade),
+            || Ok(Rc::new(self.resolve::<T>()) as Rc<dyn Any>),
+            |instance| {
+                self.record_construction(key);
+                instance
+            },
+        );
+        self.pin::<T>(key, instance)
+    }
+
+    /// Pins `instance` strongly in the singleton cache under `key`, so it
+    /// can't expire under you, and borrows it for as long as `self`.
+    fn pin<V: 'static>(&self, key: TypeId, instance: Rc<dyn Any>) -> &V {
+        self.singletons.borrow_mut().insert(key, Cached::Strong(instance.clone()));
+
+        let instance = instance.downcast::<V>().expect("singleton cache type mismatch");
+        // SAFETY: the cache now holds a strong `Rc` to this allocation and keeps
+        // it (or, once replaced, retires it) until the container drops, which
+        // the returned lifetime cannot outlive.
+        unsafe { &*Rc::as_ptr(&instance) }
+    }
+
+    /// Swaps the cached singleton of `T` for `value`, e.g. to change the logging
+    /// backend behind a feature flag.
+    ///
+    /// Ordering: every `resolve_ref`/`borrow` after this call sees `value`; an
+    /// `Rc` or `&T` obtained before keeps the old instance. A strongly cached
+    /// old instance is retired, held until the container drops since a `&T`
+    /// may still point at it (so each replacement costs its memory for the
+    /// container's lifetime); a weakly cached one is just let go.
+    ///
+    /// `value` is cached strongly whatever `T`'s scope: a replaced
+    /// `WeakSingleton` stays alive until the container drops, and is retired
+    /// like any other if replaced again. Only types resolving as a (weak)
+    /// singleton consult this cache, others never observe the swap; neither
+    /// does [`Container::resolve_copy`], which keeps its copies apart.
+    pub fn replace_singleton<T: 'static>(&self, value: T) {
+        let previous = self.singletons.borrow_mut().insert(TypeId::of::<T>(), Cached::Strong(Rc::new(value)));
+        if let Some(Cached::Strong(previous)) = previous {
+            self.retired.borrow_mut().push(previous);
+        }
+        self.record_construction(TypeId::of::<T>());
+    }
+
+    /// Moves `key` to the end of the construction order, it was (re)built last.
+    fn record_construction(&self, key: TypeId) {
+        let mut order = self.construction_order.borrow_mut();
+        order.retain(|built| *built != key);
+        order.push(key);
+    }
+
+    /// Reports whether `T` (and transitively its deps) can be resolved.
+    ///
+    /// Resolvability is a compile-time property: if the bounds hold this is
+    /// always `true`, if they don't the call site fails to compile.
+    #[inline(always)]
+    pub const fn contains<T>(&self) -> bool
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        true
+    }
+
+    /// Registers a factory for `T`, replacing any previous one.
+    ///
+    /// Meant for foreign types that can't implement `Injectable`.
+    pub fn register_factory<T: 'static>(&self, factory: impl Fn(&Container) -> T + 'static) {
+        let factory: Factory = Rc::new(move |container| Box::new(factory(container)));
+        self.record_name::<T>();
+        self.factories.borrow_mut().insert(TypeId::of::<T>(), factory);
+    }
+
+    /// Registers a prebuilt value; every resolution hands out a clone.
+    pub fn register_instance<T: Clone + 'static>(&self, instance: T) {
+        self.register_factory(move |_| instance.clone());
+    }
+
+    /// Registers a factory for `T` under `name`, alongside any other names.
+    pub fn register_named<T: 'static>(&self, name: impl Into<String>, factory: impl Fn(&Container) -> T + 'static) {
+        let factory: Factory = Rc::new(move |container| Box::new(factory(container)));
+        self.record_name::<T>();
+        self.named
+            .borrow_mut()
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .insert(name.into(), factory);
+    }
+
+    /// Builds every `T` registered by name, keyed by that name, e.g. a
+    /// `HashMap<String, Handler>` dispatch table.
+    ///
+    /// A parent's (see [`Container::with_parent`]) are included, unless a
+    /// local registration shadows the name.
+    pub fn resolve_named_map<T: 'static>(&self) -> HashMap<String, T> {
+        let mut map = self.parent.as_ref().map(|parent| parent.resolve_named_map::<T>()).unwrap_or_default();
+
+        // Never hold the borrow while building, factories may register too.
+        // A sealed table wins over the registry, as in `resolve_named`
+        let key = TypeId::of::<T>();
+        let mut factories = self.named.borrow().get(&key).cloned().unwrap_or_default();
+        if let Some(sealed) = self.sealed.as_ref().and_then(|tables| tables.named_map(key)) {
+            factories.extend(sealed.iter().map(|(name, factory)| (name.clone(), factory.clone())));
+        }
+        for (name, factory) in factories {
+            if let Ok(value) = factory(self).downcast::<T>() {
+                map.insert(name, *value);
+            }
+        }
+        map
+    }
+
+    /// Builds the `T` registered under `name`, `None` if there is none.
+    pub fn resolve_named<T: 'static>(&self, name: &str) -> Option<T> {
+        let key = TypeId::of::<T>();
+        let factory = match self.sealed.as_ref().and_then(|tables| tables.named(key, name)) {
+            Some(factory) => Some(factory.clone()),
+            None => self.named.borrow().get(&key).and_then(|factories| factories.get(name).cloned()),
+        };
+        match factory {
+            Some(factory) => factory(self).downcast::<T>().ok().map(|value| *value),
+            None => self.parent.as_ref()?.resolve_named::<T>(name),
+        }
+    }
+
+    /// Binds the trait object `Tr` to the implementation `Impl`, replacing any
+    /// previous binding: a [`Bound<Tr>`] dependency then resolves an `Impl`.
+    ///
+    /// `coerce` turns the box into a trait object; `|imp| imp` is all it takes,
+    /// the compiler inserts the unsizing coercion.
+    ///
+    /// ```
+    /// # use singularity::container::*;
+    /// # trait Repository {}
+    /// # singularity::injectable!(() => PostgresRepository);
+    /// # impl Repository for PostgresRepository {}
+    /// # let container = Container::new();
+    /// container.bind::<dyn Repository, PostgresRepository>(|imp| imp);
+    /// # assert!(container.resolve_bound::<dyn Repository>().is_some());
+    /// ```
+    pub fn bind<Tr, Impl>(&self, coerce: impl Fn(Box<Impl>) -> Box<Tr> + 'static)
+    where
+        Tr: ?Sized + 'static,
+        Impl: Injectable + 'static,
+        Impl::Deps: ResolveDepsFrom<Self>,
+    {
+        let factory: Factory = Rc::new(move |container| Box::new(coerce(Box::new(container.resolve::<Impl>()))));
+        self.record_name::<Tr>();
+        self.bindings.borrow_mut().insert(TypeId::of::<Tr>(), factory);
+    }
+
+    /// Binds the callback trait object `Tr` (`dyn Fn(Event)`, `dyn FnMut() -> u32`, ...)
+    /// to a closure: a [`Bound<Tr>`] dependency then resolves a boxed clone of it.
+    ///
+    /// Each resolution gets its own clone, so `FnMut` state isn't shared; capture
+    /// an `Rc` for that. `coerce` works as in [`Container::bind`].
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use std::rc::Rc;
+    /// # use singularity::container::*;
+    /// # struct Event;
+    /// # let container = Container::new();
+    /// # let seen = Rc::new(RefCell::new(Vec::new()));
+    /// # let log = seen.clone();
+    /// container.register_callback::<dyn Fn(Event), _>(move |event: Event| log.borrow_mut().push(event), |f| f);
+    /// # (container.resolve_bound::<dyn Fn(Event)>().unwrap())(Event);
+    /// # assert_eq!(seen.borrow().len(), 1);
+    /// ```
+    pub fn register_callback<Tr, F>(&self, callback: F, coerce: impl Fn(Box<F>) -> Box<Tr> + 'static)
+    where
+        Tr: ?Sized + 'static,
+        F: Clone + 'static,
+    {
+        let factory: Factory = Rc::new(move |_| Box::new(coerce(Box::new(callback.clone()))));
+        self.record_name::<Tr>();
+        self.bindings.borrow_mut().insert(TypeId::of::<Tr>(), factory);
+    }
+
+    /// Binds `Tr` to one of two constructors, e.g. to A/B test implementations
+    /// behind a flag. `condition` is read once, here; later resolutions all use
+    /// the constructor it picked.
+    ///
+    /// ```
+    /// # use singularity::container::*;
+    /// # trait Repository {}
+    /// # singularity::injectable!(() => PostgresRepository);
+    /// # singularity::injectable!(() => SqliteRepository);
+    /// # impl Repository for PostgresRepository {}
+    /// # impl Repository for SqliteRepository {}
+    /// # struct Flags { use_postgres: bool }
+    /// # let flags = Flags { use_postgres: true };
+    /// # let container = Container::new();
+    /// container.bind_conditional::<dyn Repository>(
+    ///     flags.use_postgres,
+    ///     |c| Box::new(c.resolve::<PostgresRepository>()),
+    ///     |c| Box::new(c.resolve::<SqliteRepository>()),
+    /// );
+    /// # assert!(container.resolve_bound::<dyn Repository>().is_some());
+    /// ```
+    pub fn bind_conditional<Tr>(
+        &self,
+        condition: bool,
+        when_true: impl Fn(&Container) -> Box<Tr> + 'static,
+        when_false: impl Fn(&Container) -> Box<Tr> + 'static,
+    ) where
+        Tr: ?Sized + 'static,
+    {
+        let chosen: Keyed<Tr> = if condition { Rc::new(when_true) } else { Rc::new(when_false) };
+        let factory: Factory = Rc::new(move |container| Box::new(chosen(container)));
+        self.record_name::<Tr>();
+        self.bindings.borrow_mut().insert(TypeId::of::<Tr>(), factory);
+    }
+
+    /// Builds whatever is bound to `Tr`, `None` if nothing is.
+    pub fn resolve_bound<Tr: ?Sized + 'static>(&self) -> Option<Box<Tr>> {
+        let factory = self.lookup(&self.bindings, TypeId::of::<Tr>(), sealed_container::Tables::binding);
+        match factory {
+            Some(factory) => factory(self).downcast::<Box<Tr>>().ok().map(|implementation| *implementation),
+            None => self.parent.as_ref()?.resolve_bound::<Tr>(),
+        }
+    }
+
+    /// Has every cached `T` disposed as its cache goes away: singletons when the
+    /// last container sharing them drops (in reverse construction order, each
+    /// right before it drops), `Scoped` instances when their child scope does.
+    ///
+    /// An instance still shared outside the container by then is skipped, it's
+    /// no longer the container's to release; transients never were.
+    pub fn register_disposable<T: Disposable + 'static>(&self) {
+        self.record_name::<T>();
+        self.disposers.borrow_mut().insert(TypeId::of::<T>(), disposable::dispose_as::<T>);
+    }
+
+    /// Disposes a cached instance on its way out, if it's registered and unshared.
+    fn dispose(&self, key: TypeId, mut instance: Rc<dyn Any>) {
+        let disposer = self.disposers.borrow().get(&key).copied();
+        if let (Some(dispose), Some(instance)) = (disposer, Rc::get_mut(&mut instance)) {
+            dispose(instance);
+        }
+    }
+
+    /// Overrides `T::SCOPE` at runtime, e.g. from a parsed config.
+    pub fn set_scope<T: 'static>(&self, scope: Scope) {
+        self.record_name::<T>();
+        self.scopes.borrow_mut().insert(TypeId::of::<T>(), scope);
+    }
+
+    /// Effective scope of `T`: the runtime override, else `T::SCOPE`,
+    /// as passed through the container's [`ScopePolicy`].
+    ///
+    /// With the `static-container` feature this is always `Transient`.
+    pub fn scope_of<T: Injectable + 'static>(&self) -> Scope {
+        if cfg!(feature = "static-container") {
+            return Scope::Transient;
+        }
+        let key = TypeId::of::<T>();
+        let declared = self.scopes.borrow().get(&key).copied().unwrap_or(T::SCOPE);
+        self.policy.scope(key, declared)
+    }
+
+    /// Remembers `T`'s name, `TypeId` alone can't be printed meaningfully.
+    fn record_name<T: ?Sized + 'static>(&self) {
+        self.type_names.borrow_mut().insert(TypeId::of::<T>(), std::any::type_name::<T>());
+    }
+
+    /// Reports whether a runtime factory is registered for `T`.
+    pub fn is_registered<T: 'static>(&self) -> bool {
+        self.lookup(&self.factories, TypeId::of::<T>(), sealed_container::Tables::factory).is_some()
+            || self.parent.as_ref().is_some_and(|parent| parent.is_registered::<T>())
+    }
+
+    /// Builds `T` from its registered factory, `None` if nothing is registered
+    /// (here or, see [`Container::with_parent`], in a parent).
+    pub fn resolve_registered<T: 'static>(&self) -> Option<T> {
+        let factory = self.lookup(&self.factories, TypeId::of::<T>(), sealed_container::Tables::factory);
+        match factory {
+            Some(factory) => factory(self).downcast::<T>().ok().map(|value| *value),
+            None => self.parent.as_ref()?.resolve_registered::<T>(),
+        }
+    }
+
+    /// The factory for `key` in the sealed `table`, else in `registry`.
+    fn lookup(
+        &self,
+        registry: &Shared<HashMap<TypeId, Factory>>,
+        key: TypeId,
+        table: fn(&sealed_container::Tables, TypeId) -> Option<&Factory>,
+    ) -> Option<Factory> {
+        match self.sealed.as_ref().and_then(|tables| table(tables, key)) {
+            Some(factory) => Some(factory.clone()),
+            None => registry.borrow().get(&key).cloned(),
+        }
+    }
+
+    /// Resolves `T`'s invoke deps and runs it, discarding the output.
+    pub fn invoke<T>(&self)
+    where
+        T: Invokable,
+        <T as Invokable>::Deps: ResolveDepsFrom<Self>,
+    {
+        let Ok(()) = self.build::<T, _, _, Infallible>(|| Ok(<T as Invokable>::Deps::resolve_deps(self)), |deps| {
+            T::invoke(deps);
+            Ok(())
+        });
+    }
+
+    /// Resolves `T`'s invoke deps and runs it, passing the output to `callback`.
+    pub fn invoke_with<T>(&self, callback: impl FnOnce(T::Output))
+    where
+        T: Invokable,
+        <T as Invokable>::Deps: ResolveDepsFrom<Self>,
+    {
+        let Ok(()) = self.build::<T, _, _, Infallible>(
+            || Ok(<T as Invokable>::Deps::resolve_deps(self)),
+            |deps| {
+                T::invoke_with(deps, callback);
+                Ok(())
+            },
+        );
+    }
 }
 
+/// Lists runtime registrations by type name, sorted so the output is stable.
+impl fmt::Debug for Container {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let type_names = self.type_names.borrow();
+        let name = |id: &TypeId| type_names.get(id).copied().unwrap_or("<unnamed>");
+
+        let mut factories: Vec<_> = self.factories.borrow().keys().map(name).collect();
+        factories.sort_unstable();
+
+        let mut named: Vec<_> = self.named.borrow().iter()
+            .map(|(id, factories)| {
+                let mut names: Vec<_> = factories.keys().cloned().collect();
+                names.sort_unstable();
+                (name(id), names)
+            })
+            .collect();
+        named.sort_unstable();
+
+        let mut bindings: Vec<_> = self.bindings.borrow().keys().map(name).collect();
+        bindings.sort_unstable();
+
+        let mut scopes: Vec<_> = self.scopes.borrow().iter().map(|(id, scope)| (name(id), *scope)).collect();
+        scopes.sort_unstable_by_key(|&(name, _)| name);
+
+        f.debug_struct("Container")
+            .field("factories", &factories)
+            .field("named", &named)
+            .field("bindings", &bindings)
+            .field("scopes", &scopes)
+            .field("singletons", &self.singletons.borrow().len())
+            .finish()
+    }
+}
+
+/// Singletons drop in reverse construction order, so an instance goes before
+/// the singletons it was built from; everything else follows in no set order.
+impl Drop for Container {
+    fn drop(&mut self) {
+        // A scope's instances go with the last handle onto it
+        if let Some(scoped) = self.scoped.take() && Rc::strong_count(&scoped) == 1 {
+            let instances = std::mem::take(&mut *scoped.borrow_mut());
+            for (key, instance) in instances {
+                self.dispose(key, instance);
+            }
+        }
+
+        // Child scopes share the cache, only the last container out tears it down
+        if Rc::strong_count(&self.singletons) > 1 {
+            return;
+        }
+
+        let order = std::mem::take(&mut *self.construction_order.borrow_mut());
+        for key in order.into_iter().rev() {
+            // Removed first, disposed and dropped after the borrow ends
+            let entry = self.singletons.borrow_mut().remove(&key);
+            match entry {
+                Some(Cached::Strong(instance)) => self.dispose(key, instance),
+                entry => drop(entry),
+            }
+        }
+    }
+}
+
+impl Resolver for Container {
+    /// Same as the inherent [`Container::resolve`], profiling and tracing included.
+    #[inline(always)]
+    fn resolve<T>(&self) -> T
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        Container::resolve(self)
+    }
+
+    /// Same as the inherent [`Container::resolve_checked`].
+    #[inline(always)]
+    fn resolve_checked<T>(&self) -> Result<T, ResolveError>
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        Container::resolve_checked(self)
+    }
+}
+
+impl Default for Container {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+#[cfg(test)]
+mod container_test;
+// Swaps in a counting global allocator, so it gets a test binary of its own feature set
+#[cfg(all(test, feature = "static-container", not(any(feature = "profiling", feature = "metrics"))))]
+mod static_container_test;
@@ -0,0 +1,50 @@
+﻿use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+
+use super::scope::Scope;
+
+
+/// Like [`Injectable`](super::Injectable), for services whose construction awaits.
+///
+/// `Deps` still resolve synchronously; only the final construction step is async.
+/// Resolve through [`Container::resolve_async`](super::Container::resolve_async).
+pub trait AsyncInjectable: Sized {
+    type Deps;
+    const SCOPE: Scope = Scope::Scoped;
+    /// See [`Injectable::DEP_COUNT`](super::Injectable::DEP_COUNT).
+    const DEP_COUNT: usize = 0;
+    fn inject_async(deps: Self::Deps) -> impl Future<Output = Self>;
+}
+
+
+/// Returned by [`Container::resolve_async_timeout`](super::Container::resolve_async_timeout)
+/// when the timer fires before the service is built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveTimeout {
+    type_name: &'static str,
+    after: Duration,
+}
+
+impl ResolveTimeout {
+    pub(crate) fn new<T>(after: Duration) -> Self {
+        Self { type_name: std::any::type_name::<T>(), after }
+    }
+
+    /// The timeout that ran out.
+    pub fn after(&self) -> Duration {
+        self.after
+    }
+}
+
+impl fmt::Display for ResolveTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "resolving `{}` timed out after {:?}", self.type_name, self.after)
+    }
+}
+
+impl std::error::Error for ResolveTimeout {}
+
+
+#[cfg(test)]
+mod async_injectable_test;
@@ -0,0 +1,100 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use rstest::*;
+use crate::container::{Container, Injectable};
+use super::*;
+
+
+/// Ready after being polled `polls` more times, rescheduling itself meanwhile.
+struct Ticks {
+    polls: u32,
+}
+
+impl Future for Ticks {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.polls == 0 {
+            return Poll::Ready(());
+        }
+        self.polls -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Stands in for a runtime timer, one poll per millisecond.
+fn sleep(after: Duration) -> Ticks {
+    Ticks { polls: after.as_millis() as u32 }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+
+struct Endpoint(&'static str);
+
+impl Injectable for Endpoint {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("db:5432")
+    }
+}
+
+/// Connecting takes 10 polls
+struct Connection(&'static str);
+
+impl AsyncInjectable for Connection {
+    type Deps = Endpoint;
+
+    async fn inject_async(endpoint: Self::Deps) -> Self {
+        Ticks { polls: 10 }.await;
+        Self(endpoint.0)
+    }
+}
+
+
+#[rstest]
+fn it_gives_up_on_slow_construction() {
+    let timeout = Duration::from_millis(3);
+
+    let err = block_on(Container::new().resolve_async_timeout::<Connection, _>(timeout, sleep))
+        .err()
+        .expect("the timer fires first");
+
+    assert_eq!(err.after(), timeout);
+    assert!(err.to_string().contains("Connection` timed out after 3ms"), "{err}");
+}
+
+
+#[rstest]
+fn it_resolves_when_construction_beats_the_timer() {
+    let connection = block_on(Container::new().resolve_async_timeout::<Connection, _>(Duration::from_millis(50), sleep))
+        .expect("connects in time");
+
+    assert_eq!(connection.0, "db:5432");
+}
+
+
+#[cfg(feature = "metrics")]
+#[rstest]
+fn it_counts_async_builds_like_any_other() {
+    let container = Container::new();
+
+    assert_eq!(block_on(container.resolve_async::<Connection>()).0, "db:5432");
+
+    let counts = container.resolution_counts();
+    assert_eq!(counts[std::any::type_name::<Connection>()], 1);
+    assert_eq!(counts[std::any::type_name::<Endpoint>()], 1);
+}
@@ -0,0 +1,34 @@
+﻿use super::resolve_deps_from::ResolveDepsFrom;
+
+
+/// A trait object built through a binding made with
+/// [`Container::bind`](super::Container::bind).
+///
+/// `type Deps = Bound<dyn Repository>;`
+///
+/// A wrapper rather than a bare `Box<dyn Trait>`: `Box` is a fundamental type,
+/// so an impl for it would overlap the one covering every `Injectable`.
+/// Panics during resolution if nothing is bound to `Tr`.
+pub struct Bound<Tr: ?Sized>(pub Box<Tr>);
+
+dependency_wrapper!(Bound<Tr: ?Sized>, Box<Tr> => Tr, "Unwraps the boxed implementation.");
+
+
+/// Consults the binding table.
+impl<Tr: ?Sized + 'static> ResolveDepsFrom<super::Container> for Bound<Tr> {
+    fn resolve_deps(container: &super::Container) -> Self {
+        Self::try_resolve_deps(container).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    fn try_resolve_deps(container: &super::Container) -> Result<Self, super::ResolveError> {
+        container.resolve_bound::<Tr>().map(Bound).ok_or_else(super::ResolveError::missing_binding::<Tr>)
+    }
+}
+
+
+/// `None` instead of a panic when nothing is bound.
+impl<Tr: ?Sized + 'static> ResolveDepsFrom<super::Container> for Option<Bound<Tr>> {
+    fn resolve_deps(container: &super::Container) -> Self {
+        container.resolve_bound::<Tr>().map(Bound)
+    }
+}
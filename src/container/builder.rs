@@ -0,0 +1,72 @@
+﻿use super::{Container, Scope, SealedContainer};
+
+
+/// Collects runtime registrations, then hands out a [`SealedContainer`].
+///
+/// Keeps configuration apart from resolution, nothing can be registered once
+/// the container is built:
+///
+/// ```
+/// use singularity::container::*;
+///
+/// let container = Container::builder()
+///     .instance(String::from("postgres://localhost"))
+///     .named("primary", |_| 5432_u16)
+///     .build();
+///
+/// assert_eq!(container.resolve_registered::<String>().unwrap(), "postgres://localhost");
+/// assert_eq!(container.resolve_named::<u16>("primary"), Some(5432));
+/// ```
+pub struct ContainerBuilder {
+    container: Container,
+}
+
+impl ContainerBuilder {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// See [`Container::with_capacity`].
+    pub fn with_capacity(capacity: usize) -> Self {
+        ContainerBuilder { container: Container::with_capacity(capacity) }
+    }
+
+    /// See [`Container::register_factory`].
+    pub fn factory<T: 'static>(self, factory: impl Fn(&Container) -> T + 'static) -> Self {
+        self.container.register_factory(factory);
+        self
+    }
+
+    /// See [`Container::register_instance`].
+    pub fn instance<T: Clone + 'static>(self, instance: T) -> Self {
+        self.container.register_instance(instance);
+        self
+    }
+
+    /// See [`Container::set_scope`].
+    pub fn scope<T: 'static>(self, scope: Scope) -> Self {
+        self.container.set_scope::<T>(scope);
+        self
+    }
+
+    /// See [`Container::register_named`].
+    pub fn named<T: 'static>(self, name: impl Into<String>, factory: impl Fn(&Container) -> T + 'static) -> Self {
+        self.container.register_named(name, factory);
+        self
+    }
+
+    /// Finishes configuration, see [`Container::seal`].
+    pub fn build(self) -> SealedContainer {
+        self.container.seal()
+    }
+}
+
+impl Default for ContainerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[cfg(test)]
+mod builder_test;
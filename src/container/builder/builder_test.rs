@@ -0,0 +1,66 @@
+﻿
+
+use std::rc::Rc;
+
+use rstest::*;
+use super::super::*;
+
+
+#[derive(Clone)]
+struct DbUrl(&'static str);
+
+struct Clock(u64);
+
+struct Repo {
+    url: Registered<DbUrl>,
+    clock: Registered<Clock>,
+}
+
+impl Injectable for Repo {
+    type Deps = (Registered<DbUrl>, Registered<Clock>);
+
+    fn inject((url, clock): Self::Deps) -> Self {
+        Self { url, clock }
+    }
+}
+
+struct Cache(#[allow(dead_code)] usize);
+
+impl Injectable for Cache {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(64)
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_builds_a_container_from_mixed_registrations() {
+    let container = ContainerBuilder::new()
+        .instance(DbUrl("postgres://primary"))
+        .factory(|_| Clock(42))
+        .scope::<Cache>(Scope::Singleton)
+        .named("replica", |_| DbUrl("postgres://replica"))
+        .build();
+
+    let repo = container.resolve::<Repo>();
+    assert_eq!(repo.url.0.0, "postgres://primary");
+    assert_eq!(repo.clock.0.0, 42);
+
+    assert_eq!(container.scope_of::<Cache>(), Scope::Singleton);
+    assert!(Rc::ptr_eq(&container.resolve_ref::<Cache>(), &container.resolve_ref::<Cache>()));
+
+    assert_eq!(container.resolve_named::<DbUrl>("replica").map(|url| url.0), Some("postgres://replica"));
+    assert!(container.resolve_named::<DbUrl>("missing").is_none());
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_keeps_default_scopes_without_overrides() {
+    let container = Container::builder().build();
+
+    assert_eq!(container.scope_of::<Cache>(), Scope::Scoped);
+}
@@ -0,0 +1,25 @@
+﻿use super::resolve_deps_from::DepsTuple;
+use super::Injectable;
+
+#[cfg(feature = "derive")]
+pub use singularity_proc_macros::ResolveBundle;
+
+
+/// A tuple resolved as a service: `container.resolve::<Bundle<(A, B)>>()`
+/// builds every element, like a `Deps` list, and hands them back together.
+///
+/// Tuples themselves only ever are dependency lists (see [`DepsTuple`]), the
+/// wrapper makes asking for one as a service explicit. For named fields,
+/// `#[derive(ResolveBundle)]` on a struct does the same.
+pub struct Bundle<T>(pub T);
+
+dependency_wrapper!(Bundle<T>, T => T, "Unwraps the resolved tuple.");
+
+impl<T: DepsTuple> Injectable for Bundle<T> {
+    type Deps = T;
+
+    #[inline(always)]
+    fn inject(deps: Self::Deps) -> Self {
+        Bundle(deps)
+    }
+}
@@ -0,0 +1,57 @@
+﻿use std::any::Any;
+use std::rc::{Rc, Weak};
+use std::sync::{self, Arc};
+
+
+/// A singleton cache slot.
+pub(crate) enum Cached {
+    /// Kept alive by the container.
+    Strong(Rc<dyn Any>),
+    /// Kept alive only by outside handles.
+    Weak(Weak<dyn Any>),
+}
+
+impl Cached {
+    /// Returns the live instance, `None` if a weak slot has expired.
+    pub(crate) fn upgrade(&self) -> Option<Rc<dyn Any>> {
+        match self {
+            Cached::Strong(instance) => Some(instance.clone()),
+            Cached::Weak(instance) => instance.upgrade(),
+        }
+    }
+}
+
+/// A [`SyncContainer`](super::SyncContainer) singleton cache slot.
+pub(crate) enum SyncCached {
+    /// Kept alive by the container.
+    Strong(Arc<dyn Any + Send + Sync>),
+    /// Kept alive only by outside handles.
+    Weak(sync::Weak<dyn Any + Send + Sync>),
+}
+
+impl SyncCached {
+    /// Returns the live instance, `None` if a weak slot has expired.
+    pub(crate) fn upgrade(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        match self {
+            SyncCached::Strong(instance) => Some(instance.clone()),
+            SyncCached::Weak(instance) => instance.upgrade(),
+        }
+    }
+}
+
+/// The lookup every cache goes through: `get`, or on a miss `build` and hand
+/// the new instance to `store`, which returns what the caller gets. A failed
+/// `build` stores nothing.
+///
+/// Never hold a borrow (or lock) while building, deps may hit the cache too:
+/// `get` and `store` each take their own.
+pub(crate) fn get_or_build<V, E>(
+    get: impl FnOnce() -> Option<V>,
+    build: impl FnOnce() -> Result<V, E>,
+    store: impl FnOnce(V) -> V,
+) -> Result<V, E> {
+    match get() {
+        Some(value) => Ok(value),
+        None => build().map(store),
+    }
+}
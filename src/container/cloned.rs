@@ -0,0 +1,36 @@
+﻿use super::resolve_deps_from::ResolveDepsFrom;
+use super::{Container, Injectable, ResolveError, Scope};
+
+
+/// An owned `T` cloned from the instance [`Container::resolve_ref`] hands out,
+/// so a singleton is built once however many services want their own copy:
+/// `type Deps = (Cloned<Config>, Rc<Config>);` both come from the one cached
+/// `Config`.
+///
+/// A plain `T` dep is always built fresh; telling `Clone` singletons apart
+/// there would take specialization, so the copy is asked for by name.
+pub struct Cloned<T>(pub T);
+
+dependency_wrapper!(Cloned<T>, T => T, "Unwraps the copy.");
+
+/// Transients have no instance to clone and are resolved directly, as
+/// [`Container::resolve_vec`] does.
+impl<T> ResolveDepsFrom<Container> for Cloned<T>
+where
+    T: Injectable + Clone + 'static,
+    T::Deps: ResolveDepsFrom<Container>,
+{
+    fn resolve_deps(container: &Container) -> Self {
+        if container.scope_of::<T>() == Scope::Transient {
+            return Cloned(container.resolve::<T>());
+        }
+        Cloned(T::clone(&container.resolve_ref::<T>()))
+    }
+
+    fn try_resolve_deps(container: &Container) -> Result<Self, ResolveError> {
+        if container.scope_of::<T>() == Scope::Transient {
+            return container.resolve_checked::<T>().map(Cloned);
+        }
+        container.resolve_ref_checked::<T>().map(|shared| Cloned(T::clone(&shared)))
+    }
+}
@@ -0,0 +1,1287 @@
+﻿
+
+use rstest::*;
+use super::*;
+
+
+struct Leaf(i32);
+struct Branch(Leaf);
+
+impl Injectable for Leaf {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(1)
+    }
+}
+
+impl Injectable for Branch {
+    type Deps = Leaf;
+
+    fn inject(deps: Self::Deps) -> Self {
+        Self(deps)
+    }
+}
+
+
+#[rstest]
+fn it_contains_resolvable_types() {
+    let container = Container::new();
+
+    assert!(container.contains::<Leaf>());
+    assert!(container.contains::<Branch>());
+    assert_eq!(container.resolve::<Branch>().0.0, 1);
+}
+
+
+#[rstest]
+fn it_constructs_from_hand_built_dependencies() {
+    let container = Container::new();
+
+    let constructed = container.construct::<Branch>(Leaf(1));
+    assert_eq!(constructed.0.0, container.resolve::<Branch>().0.0);
+
+    let overridden: Branch = container.construct(Leaf(42));
+    assert_eq!(overridden.0.0, 42);
+}
+
+
+/// Stands in for a third-party type that can't implement `Injectable`.
+struct ForeignClient {
+    base_url: String,
+}
+
+struct ApiService {
+    client: Registered<ForeignClient>,
+    leaf: Leaf,
+}
+
+impl Injectable for ApiService {
+    type Deps = (Registered<ForeignClient>, Leaf);
+
+    fn inject((client, leaf): Self::Deps) -> Self {
+        Self { client, leaf }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_dependencies_from_registered_factories() {
+    let container = Container::new();
+    assert!(!container.is_registered::<ForeignClient>());
+
+    container.register_factory(|_| ForeignClient { base_url: "http://localhost".into() });
+    assert!(container.is_registered::<ForeignClient>());
+
+    let svc = container.resolve::<ApiService>();
+    assert_eq!(svc.client.base_url, "http://localhost");
+    assert_eq!(svc.leaf.0, 1);
+}
+
+
+#[derive(Clone)]
+struct RegionConfig(&'static str);
+struct RegionCache(usize);
+
+impl Injectable for RegionConfig {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("default")
+    }
+}
+
+impl Injectable for RegionCache {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+struct RegionService(Preferred<RegionConfig>, RegionCache);
+
+impl Injectable for RegionService {
+    type Deps = (Preferred<RegionConfig>, RegionCache);
+
+    fn inject((config, cache): Self::Deps) -> Self {
+        Self(config, cache)
+    }
+}
+
+
+#[rstest]
+fn it_mixes_registered_instances_and_injected_deps() {
+    let container = Container::new();
+    assert_eq!(container.resolve::<RegionService>().0.0.0, "default");
+
+    container.register_instance(RegionConfig("eu-west"));
+
+    let RegionService(config, cache) = container.resolve();
+    assert_eq!(config.0.0, "eu-west");
+    assert_ne!(cache.0, container.resolve::<RegionCache>().0, "unregistered deps are still built");
+}
+
+
+#[rstest]
+fn it_passes_the_container_to_factories() {
+    let container = Container::new();
+    container.register_factory(|c| ForeignClient {
+        base_url: format!("http://leaf-{}", c.resolve::<Leaf>().0),
+    });
+
+    let client = container.resolve_registered::<ForeignClient>().unwrap();
+    assert_eq!(client.base_url, "http://leaf-1");
+}
+
+
+#[rstest]
+fn it_reports_unregistered_types() {
+    let container = Container::new();
+
+    assert!(!container.is_registered::<ForeignClient>());
+    assert!(container.resolve_registered::<ForeignClient>().is_none());
+}
+
+
+#[rstest]
+#[should_panic(expected = "no factory registered for")]
+fn it_panics_when_a_registered_dependency_is_missing() {
+    Container::new().resolve::<ApiService>();
+}
+
+
+static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Every construction gets a fresh id.
+fn next_id() -> usize {
+    NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+struct SharedConfig(usize);
+
+impl Injectable for SharedConfig {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+struct WeakConfig(usize);
+
+impl Injectable for WeakConfig {
+    type Deps = ();
+    const SCOPE: Scope = Scope::WeakSingleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_caches_singletons_in_resolve_ref() {
+    let container = Container::new();
+
+    let first = container.resolve_ref::<SharedConfig>();
+    drop(container.resolve_ref::<SharedConfig>());
+    let second = container.resolve_ref::<SharedConfig>();
+
+    assert!(Rc::ptr_eq(&first, &second));
+    assert_eq!(first.0, second.0);
+}
+
+
+#[rstest]
+fn it_builds_transient_handles_fresh() {
+    let container = Container::new();
+
+    let first = container.resolve_ref::<Leaf>();
+    let second = container.resolve_ref::<Leaf>();
+
+    assert!(!Rc::ptr_eq(&first, &second));
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_rebuilds_weak_singletons_after_all_handles_drop() {
+    let container = Container::new();
+
+    let first = container.resolve_ref::<WeakConfig>();
+    let again = container.resolve_ref::<WeakConfig>();
+    assert!(Rc::ptr_eq(&first, &again), "live weak singleton must be shared");
+
+    let first_id = first.0;
+    drop(first);
+    drop(again);
+
+    let rebuilt = container.resolve_ref::<WeakConfig>();
+    assert_ne!(rebuilt.0, first_id, "a new instance must be created");
+    assert_eq!(Rc::strong_count(&rebuilt), 1, "container must not hold it strongly");
+}
+
+
+#[derive(Clone, Hash, PartialEq, Eq, Debug)]
+enum Channel {
+    Email,
+    Sms,
+}
+
+trait Handler {
+    fn handle(&self) -> String;
+}
+
+struct EmailHandler(Leaf);
+struct SmsHandler;
+
+impl Injectable for EmailHandler {
+    type Deps = Leaf;
+
+    fn inject(deps: Self::Deps) -> Self {
+        Self(deps)
+    }
+}
+
+impl Handler for EmailHandler {
+    fn handle(&self) -> String {
+        format!("email-{}", self.0.0)
+    }
+}
+
+impl Handler for SmsHandler {
+    fn handle(&self) -> String {
+        "sms".into()
+    }
+}
+
+
+#[rstest]
+fn it_resolves_keyed_handlers_into_a_map() {
+    let container = Container::new();
+    container.register_keyed::<Channel, dyn Handler>(Channel::Email, |c| Box::new(c.resolve::<EmailHandler>()));
+    container.register_keyed::<Channel, dyn Handler>(Channel::Sms, |_| Box::new(SmsHandler));
+
+    let handlers = container.resolve_map::<Channel, dyn Handler>();
+
+    assert_eq!(handlers.len(), 2);
+    assert_eq!(handlers[&Channel::Email].handle(), "email-1");
+    assert_eq!(handlers[&Channel::Sms].handle(), "sms");
+}
+
+
+#[rstest]
+fn it_resolves_an_empty_map_without_registrations() {
+    let container = Container::new();
+
+    assert!(container.resolve_map::<Channel, dyn Handler>().is_empty());
+}
+
+
+struct Notifier {
+    leaf: Option<Leaf>,
+    mailer: Option<Registered<ForeignClient>>,
+}
+
+impl Injectable for Notifier {
+    type Deps = (Option<Leaf>, Option<Registered<ForeignClient>>);
+
+    fn inject((leaf, mailer): Self::Deps) -> Self {
+        Self { leaf, mailer }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_optional_dependencies_when_present() {
+    let container = Container::new();
+    container.register_factory(|_| ForeignClient { base_url: "smtp://mail".into() });
+
+    let notifier = container.resolve::<Notifier>();
+
+    assert_eq!(notifier.leaf.map(|leaf| leaf.0), Some(1));
+    assert_eq!(notifier.mailer.map(|m| m.into_inner().base_url).as_deref(), Some("smtp://mail"));
+}
+
+
+#[rstest]
+fn it_resolves_optional_dependencies_as_none_when_unregistered() {
+    let notifier = Container::new().resolve::<Notifier>();
+
+    assert!(notifier.leaf.is_some(), "Injectables are always resolvable");
+    assert!(notifier.mailer.is_none());
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_preallocates_the_singleton_cache() {
+    let container = Container::with_capacity(64);
+    assert!(container.singletons.borrow().capacity() >= 64);
+
+    container.resolve_ref::<SharedConfig>();
+    assert_eq!(container.singletons.borrow().len(), 1);
+    assert!(container.singletons.borrow().capacity() >= 64);
+
+    assert_eq!(Container::new().singletons.borrow().capacity(), 0);
+}
+
+
+#[cfg(feature = "metrics")]
+#[rstest]
+fn it_counts_resolutions_per_type() {
+    let container = Container::new();
+
+    for _ in 0..3 {
+        container.resolve::<Branch>();
+    }
+
+    let counts = container.resolution_counts();
+    assert_eq!(counts[std::any::type_name::<Branch>()], 3);
+    assert_eq!(counts[std::any::type_name::<Leaf>()], 3, "each branch builds its own leaf");
+}
+
+
+#[cfg(feature = "profiling")]
+struct SlowService(Leaf);
+
+#[cfg(feature = "profiling")]
+impl Injectable for SlowService {
+    type Deps = Leaf;
+
+    fn inject(deps: Self::Deps) -> Self {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        Self(deps)
+    }
+}
+
+
+#[cfg(feature = "profiling")]
+#[rstest]
+fn it_records_inject_timings() {
+    let container = Container::new();
+    assert_eq!(container.resolve::<SlowService>().0.0, 1);
+
+    let timings = container.timings();
+    let (slowest, took) = timings[0];
+
+    assert_eq!(slowest, std::any::type_name::<SlowService>());
+    assert!(took >= std::time::Duration::from_millis(5));
+    assert!(timings.iter().any(|(name, _)| *name == std::any::type_name::<Leaf>()));
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_borrows_singletons_from_the_cache() {
+    let container = Container::new();
+
+    let config: &SharedConfig = container.borrow::<SharedConfig>();
+    let again: &SharedConfig = container.borrow::<SharedConfig>();
+    let shared = container.resolve_ref::<SharedConfig>();
+
+    assert!(std::ptr::eq(config, again));
+    assert!(std::ptr::eq(config, Rc::as_ptr(&shared)));
+    assert_eq!(config.0, shared.0);
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_pins_weak_singletons_while_borrowed() {
+    let container = Container::new();
+
+    let config = container.borrow::<WeakConfig>();
+    let id = config.0;
+
+    // No outside Rc exists, the pinned instance must survive anyway
+    assert_eq!(container.resolve_ref::<WeakConfig>().0, id);
+    assert_eq!(config.0, id);
+}
+
+
+struct RequestId(usize);
+
+impl Injectable for RequestId {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_caches_scoped_instances_per_child() {
+    let root = Container::new();
+    let first = root.child();
+    let second = root.child();
+
+    let a = first.resolve_ref::<RequestId>();
+    let b = first.resolve_scoped::<RequestId>();
+    let c = second.resolve_ref::<RequestId>();
+
+    assert!(Rc::ptr_eq(&a, &b), "same child must share the instance");
+    assert!(!Rc::ptr_eq(&a, &c), "sibling children must not share");
+    assert_ne!(a.0, c.0);
+}
+
+
+#[rstest]
+fn it_builds_scoped_instances_fresh_on_the_root() {
+    let root = Container::new();
+
+    assert!(!Rc::ptr_eq(&root.resolve_scoped::<RequestId>(), &root.resolve_scoped::<RequestId>()));
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_shares_singletons_and_registrations_with_children() {
+    let root = Container::new();
+    root.register_factory(|_| ForeignClient { base_url: "http://root".into() });
+    let singleton = root.resolve_ref::<SharedConfig>();
+
+    let child = root.child();
+
+    assert!(Rc::ptr_eq(&singleton, &child.resolve_ref::<SharedConfig>()));
+    assert_eq!(child.resolve_registered::<ForeignClient>().unwrap().base_url, "http://root");
+}
+
+
+/// Command-style invokable: greets through the client, reports the length.
+struct SendGreeting;
+
+impl Injectable for SendGreeting {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self
+    }
+}
+
+impl Invokable for SendGreeting {
+    type Deps = (Leaf, Registered<ForeignClient>);
+    type Output = String;
+
+    fn invoke_with<F>((leaf, client): <Self as Invokable>::Deps, callback: F)
+    where
+        F: FnOnce(Self::Output),
+    {
+        SENT.with(|sent| sent.set(sent.get() + 1));
+        callback(format!("{}/greet/{}", client.base_url, leaf.0));
+    }
+}
+
+thread_local! {
+    static SENT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+
+#[rstest]
+fn it_invokes_with_resolved_dependencies() {
+    let container = Container::new();
+    container.register_factory(|_| ForeignClient { base_url: "http://api".into() });
+
+    container.invoke::<SendGreeting>();
+    assert_eq!(SENT.with(|sent| sent.get()), 1);
+
+    let mut output = None;
+    container.invoke_with::<SendGreeting>(|url| output = Some(url));
+    assert_eq!(output.as_deref(), Some("http://api/greet/1"));
+    assert_eq!(SENT.with(|sent| sent.get()), 2);
+}
+
+
+#[cfg(feature = "metrics")]
+struct Quota(Leaf);
+
+#[cfg(feature = "metrics")]
+impl TryInjectable for Quota {
+    type Deps = Leaf;
+    type Error = &'static str;
+
+    fn try_inject(leaf: Self::Deps) -> Result<Self, Self::Error> {
+        Ok(Self(leaf))
+    }
+}
+
+
+#[cfg(feature = "metrics")]
+#[rstest]
+fn it_counts_fallible_builds_and_invocations_too() {
+    let container = Container::new();
+    container.register_factory(|_| ForeignClient { base_url: "http://api".into() });
+
+    assert_eq!(container.try_resolve::<Quota>().map(|quota| quota.0.0), Ok(1));
+    container.invoke::<SendGreeting>();
+    container.invoke_with::<SendGreeting>(|_| {});
+
+    let counts = container.resolution_counts();
+    assert_eq!(counts[std::any::type_name::<Quota>()], 1);
+    assert_eq!(counts[std::any::type_name::<SendGreeting>()], 2);
+    assert_eq!(counts[std::any::type_name::<Leaf>()], 3);
+}
+
+
+#[rstest]
+fn it_lists_registrations_in_debug_output() {
+    let container = Container::new();
+    assert_eq!(
+        format!("{container:?}"),
+        "Container { factories: [], named: [], bindings: [], scopes: [], singletons: 0 }"
+    );
+
+    container.register_factory(|_| ForeignClient { base_url: "http://localhost".into() });
+    container.register_instance(8080_u16);
+    container.set_scope::<Leaf>(Scope::Singleton);
+
+    let debug = format!("{container:?}");
+    assert!(debug.contains(std::any::type_name::<ForeignClient>()), "{debug}");
+    assert!(debug.contains(r#"factories: ["#) && debug.contains(r#""u16""#), "{debug}");
+    assert!(debug.contains(&format!("(\"{}\", Singleton)", std::any::type_name::<Leaf>())), "{debug}");
+}
+
+
+#[derive(Clone)]
+struct Pool(&'static str);
+
+trait Logger {
+    fn name(&self) -> &'static str;
+}
+
+struct StdoutLogger;
+
+impl Logger for StdoutLogger {
+    fn name(&self) -> &'static str {
+        "stdout"
+    }
+}
+
+
+#[rstest]
+fn it_registers_instances_in_batch() {
+    let container = Container::new();
+    let pool = Pool("postgres");
+    let retries = 3_u8;
+    let logger = Rc::new(StdoutLogger);
+
+    macros::register!(container, pool, retries, logger as Rc<dyn Logger>,);
+
+    assert_eq!(container.resolve_registered::<Pool>().unwrap().0, "postgres");
+    assert_eq!(container.resolve_registered::<u8>(), Some(3));
+    assert_eq!(container.resolve_registered::<Rc<dyn Logger>>().unwrap().name(), "stdout");
+    assert!(!container.is_registered::<Rc<StdoutLogger>>());
+}
+
+
+#[derive(Clone, Copy)]
+struct Limits {
+    id: usize,
+    max_connections: u16,
+}
+
+impl Injectable for Limits {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self { id: next_id(), max_connections: 16 }
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_caches_copy_singletons_by_value() {
+    let container = Container::new();
+
+    let first = container.resolve_copy::<Limits>();
+    let second = container.resolve_copy::<Limits>();
+
+    assert_eq!(first.id, second.id, "inject must run once");
+    assert_eq!(second.max_connections, 16);
+    assert!(container.singletons.borrow().is_empty(), "no Rc must be cached");
+
+    container.set_scope::<Limits>(Scope::Transient);
+    assert_ne!(container.resolve_copy::<Limits>().id, first.id);
+}
+
+
+thread_local! {
+    static ROUND_ROBINS_BUILT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[derive(Clone, Copy)]
+struct RoundRobin;
+
+impl Injectable for RoundRobin {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        ROUND_ROBINS_BUILT.set(ROUND_ROBINS_BUILT.get() + 1);
+        Self
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_builds_a_zero_sized_singleton_once_whichever_way_it_is_resolved() {
+    let container = Container::new();
+    let built = ROUND_ROBINS_BUILT.get();
+
+    for _ in 0..100 {
+        let _: Rc<RoundRobin> = container.resolve_ref();
+        let _: RoundRobin = container.resolve_copy();
+    }
+
+    assert_eq!(ROUND_ROBINS_BUILT.get() - built, 1, "inject must run once");
+    assert!(container.copies.borrow().is_empty(), "kept in the singleton cache only");
+}
+
+
+struct Parse(usize);
+struct Validate(usize);
+struct Store(usize);
+
+impl Injectable for Parse {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+impl Injectable for Validate {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+impl Injectable for Store {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+
+#[rstest]
+fn it_resolves_all_services_in_order() {
+    let container = Container::new();
+
+    let (parse, validate, store) = crate::resolve_all!(container, Parse, Validate, Store);
+
+    assert!(parse.0 < validate.0 && validate.0 < store.0);
+}
+
+
+#[derive(Clone)]
+struct Worker(usize);
+
+impl Injectable for Worker {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Transient;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+#[derive(Clone)]
+struct Dispatcher(usize);
+
+impl Injectable for Dispatcher {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+
+#[rstest]
+fn it_resolves_distinct_transient_instances_into_a_vec() {
+    let workers = Container::new().resolve_vec::<Worker>(3);
+
+    let mut ids: Vec<_> = workers.iter().map(|w| w.0).collect();
+    ids.dedup();
+    assert_eq!(ids.len(), 3);
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_resolves_clones_of_a_singleton_into_a_vec() {
+    let container = Container::new();
+
+    let dispatchers = container.resolve_vec::<Dispatcher>(3);
+    let cached = container.resolve_ref::<Dispatcher>();
+
+    assert_eq!(dispatchers.len(), 3);
+    assert!(dispatchers.iter().all(|d| d.0 == cached.0));
+    assert!(container.resolve_vec::<Dispatcher>(0).is_empty());
+}
+
+
+#[rstest]
+fn it_hands_out_a_resolver_building_fresh_transients() {
+    let container = Container::new();
+    let spawn_worker = container.resolver::<Worker>();
+
+    let (first, second) = (spawn_worker(), spawn_worker());
+
+    assert_ne!(first.0, second.0);
+}
+
+
+#[rstest]
+fn it_lets_a_policy_force_every_scope() {
+    let container = Container::with_policy(Scope::Transient);
+
+    assert_eq!(container.scope_of::<SharedConfig>(), Scope::Transient);
+    let first = container.resolve_ref::<SharedConfig>();
+    let second = container.resolve_ref::<SharedConfig>();
+
+    assert!(!Rc::ptr_eq(&first, &second), "singletons must be built fresh");
+    assert_ne!(first.0, second.0);
+    assert!(container.singletons.borrow().is_empty());
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_passes_declared_scopes_to_the_policy() {
+    let leaf = TypeId::of::<Leaf>();
+    let container = Container::with_policy(move |id, declared| if id == leaf { Scope::Singleton } else { declared });
+
+    assert_eq!(container.scope_of::<Leaf>(), Scope::Singleton);
+    assert_eq!(container.scope_of::<SharedConfig>(), Scope::Singleton);
+    assert_eq!(container.scope_of::<WeakConfig>(), Scope::WeakSingleton);
+    assert_eq!(Container::new().scope_of::<Leaf>(), Scope::Scoped);
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_replaces_live_singletons() {
+    let container = Container::new();
+    let old = container.resolve_ref::<SharedConfig>();
+    let borrowed = container.borrow::<SharedConfig>();
+
+    container.replace_singleton(SharedConfig(usize::MAX));
+    let new = container.resolve_ref::<SharedConfig>();
+
+    assert_eq!(new.0, usize::MAX);
+    assert_eq!(container.borrow::<SharedConfig>().0, usize::MAX);
+    assert_ne!(old.0, usize::MAX, "existing handles keep the old instance");
+    assert_eq!(borrowed.0, old.0, "borrows stay valid after the swap");
+}
+
+
+trait Repository {
+    fn find(&self, id: u32) -> String;
+}
+
+struct PostgresRepository(Leaf);
+
+impl Injectable for PostgresRepository {
+    type Deps = Leaf;
+
+    fn inject(deps: Self::Deps) -> Self {
+        Self(deps)
+    }
+}
+
+impl Repository for PostgresRepository {
+    fn find(&self, id: u32) -> String {
+        format!("pg-{}-{id}", self.0.0)
+    }
+}
+
+struct UserService {
+    repo: Bound<dyn Repository>,
+}
+
+impl Injectable for UserService {
+    type Deps = Bound<dyn Repository>;
+
+    fn inject(repo: Self::Deps) -> Self {
+        Self { repo }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_trait_objects_through_bindings() {
+    let container = Container::new();
+    assert!(container.resolve_bound::<dyn Repository>().is_none());
+
+    container.bind::<dyn Repository, PostgresRepository>(|imp| imp);
+
+    assert_eq!(container.resolve::<UserService>().repo.find(42), "pg-1-42");
+    assert!(format!("{container:?}").contains("dyn"));
+}
+
+
+struct InMemoryRepository;
+
+impl Repository for InMemoryRepository {
+    fn find(&self, id: u32) -> String {
+        format!("mem-{id}")
+    }
+}
+
+
+#[rstest]
+#[case(true, "pg-1-7")]
+#[case(false, "mem-7")]
+fn it_binds_one_of_two_implementations_by_condition(#[case] condition: bool, #[case] expected: &str) {
+    let container = Container::new();
+
+    container.bind_conditional::<dyn Repository>(
+        condition,
+        |c| Box::new(c.resolve::<PostgresRepository>()),
+        |_| Box::new(InMemoryRepository),
+    );
+
+    assert_eq!(container.resolve::<UserService>().repo.find(7), expected);
+    assert_eq!(container.resolve::<UserService>().repo.find(7), expected);
+}
+
+
+#[rstest]
+#[should_panic(expected = "nothing bound to `dyn")]
+fn it_panics_when_a_trait_is_unbound() {
+    Container::new().resolve::<UserService>();
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Event {
+    Started,
+    Stopped,
+}
+
+struct EventSource {
+    on_event: Bound<dyn Fn(Event)>,
+}
+
+impl Injectable for EventSource {
+    type Deps = Bound<dyn Fn(Event)>;
+
+    fn inject(on_event: Self::Deps) -> Self {
+        Self { on_event }
+    }
+}
+
+
+#[rstest]
+fn it_injects_registered_callbacks() {
+    let container = Container::new();
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let log = seen.clone();
+    container.register_callback::<dyn Fn(Event), _>(move |event: Event| log.borrow_mut().push(event), |f| f);
+
+    let source = container.resolve::<EventSource>();
+    (source.on_event)(Event::Started);
+    (container.resolve::<EventSource>().on_event)(Event::Stopped);
+
+    assert_eq!(*seen.borrow(), [Event::Started, Event::Stopped]);
+}
+
+
+#[cfg(feature = "std")]
+struct Misconfigured;
+
+#[cfg(feature = "std")]
+impl Injectable for Misconfigured {
+    type Deps = Leaf;
+
+    fn inject(_: Self::Deps) -> Self {
+        panic!("missing API key")
+    }
+}
+
+
+#[cfg(feature = "std")]
+#[rstest]
+fn it_catches_panics_while_resolving() {
+    let container = Container::new();
+
+    let payload = container.catch_resolve::<Misconfigured>().err().expect("inject panics");
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"missing API key"));
+
+    // Still usable afterwards
+    assert_eq!(container.catch_resolve::<Branch>().map(|branch| branch.0.0).ok(), Some(1));
+}
+
+
+#[rstest]
+fn it_reports_missing_registrations_as_errors() {
+    let container = Container::new();
+
+    assert_eq!(
+        container.resolve_checked::<ApiService>().err(),
+        Some(ResolveError::missing_factory::<ForeignClient>()),
+    );
+    let missing_binding = container.resolve_checked::<UserService>().err().expect("nothing bound");
+    assert!(matches!(missing_binding, ResolveError::MissingBinding { type_id, .. } if type_id == TypeId::of::<dyn Repository>()));
+    assert_eq!(missing_binding.to_string(), format!("nothing bound to `{}`", std::any::type_name::<dyn Repository>()));
+
+    container.register_factory(|_| ForeignClient { base_url: "http://localhost".into() });
+    assert_eq!(container.resolve_checked::<ApiService>().map(|svc| svc.client.0.base_url).ok().as_deref(), Some("http://localhost"));
+}
+
+
+/// Reaches its registry-backed dep through a cached singleton
+struct ApiGateway(Rc<ApiService>);
+
+impl Injectable for ApiGateway {
+    type Deps = (Leaf, Rc<ApiService>);
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject((_, api): Self::Deps) -> Self {
+        Self(api)
+    }
+}
+
+#[rstest]
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+fn it_reports_a_miss_behind_a_shared_dep_without_caching_anything() {
+    let container = Container::new();
+    container.set_scope::<ApiService>(Scope::Singleton);
+
+    assert_eq!(
+        container.resolve_checked::<ApiGateway>().err(),
+        Some(ResolveError::missing_factory::<ForeignClient>()),
+    );
+
+    container.register_factory(|_| ForeignClient { base_url: "http://localhost".into() });
+    let gateway = container.resolve_checked::<ApiGateway>().expect("registered by now");
+    assert!(Rc::ptr_eq(&gateway.0, &container.resolve_ref::<ApiService>()));
+}
+
+
+/// Resolves what it needs only when asked
+struct Locator {
+    container: Rc<Container>,
+}
+
+impl Injectable for Locator {
+    type Deps = Rc<Container>;
+
+    fn inject(container: Self::Deps) -> Self {
+        Self { container }
+    }
+}
+
+
+#[rstest]
+fn it_injects_the_container_for_later_resolution() {
+    let container = Container::new();
+    let locator = container.resolve::<Locator>();
+
+    // Registered after the locator was built, still seen through its handle
+    container.register_instance(RegionConfig("late"));
+
+    assert_eq!(locator.container.resolve::<Branch>().0.0, 1);
+    assert_eq!(locator.container.resolve_registered::<RegionConfig>().map(|config| config.0), Some("late"));
+}
+
+
+trait Plugin {
+    fn name(&self) -> String;
+}
+
+struct AuditPlugin(Leaf);
+struct CachePlugin;
+
+impl Plugin for AuditPlugin {
+    fn name(&self) -> String {
+        format!("audit-{}", self.0.0)
+    }
+}
+
+impl Plugin for CachePlugin {
+    fn name(&self) -> String {
+        "cache".into()
+    }
+}
+
+/// Borrows its plugins from the container
+struct PluginHost<'c> {
+    plugins: &'c [Box<dyn Plugin>],
+}
+
+impl<'c> Injectable for PluginHost<'c> {
+    type Deps = &'c [Box<dyn Plugin>];
+
+    fn inject(plugins: Self::Deps) -> Self {
+        Self { plugins }
+    }
+}
+
+
+#[rstest]
+fn it_hands_out_registered_plugins_as_a_slice() {
+    let container = Container::new();
+    assert!(container.resolve_slice::<Box<dyn Fn()>>().is_empty());
+
+    container.register_plugin::<Box<dyn Plugin>>(|c| Box::new(AuditPlugin(c.resolve())));
+    container.register_plugin::<Box<dyn Plugin>>(|_| Box::new(CachePlugin));
+
+    let host = container.construct::<PluginHost>(container.resolve_slice());
+    let names: Vec<_> = host.plugins.iter().map(|plugin| plugin.name()).collect();
+    assert_eq!(names, ["audit-1", "cache"]);
+
+    // Built once, every later call borrows the same slice
+    assert!(std::ptr::eq(host.plugins, container.resolve_slice::<Box<dyn Plugin>>()));
+}
+
+
+#[rstest]
+#[cfg(feature = "std-defaults")]
+fn it_keeps_the_plugin_slice_apart_from_a_vec_singleton() {
+    let container = Container::new();
+    container.register_plugin::<&'static str>(|_| "plugin");
+
+    assert!(container.borrow::<Vec<&'static str>>().is_empty());
+    assert_eq!(container.resolve_slice::<&'static str>(), ["plugin"]);
+    assert!(container.borrow::<Vec<&'static str>>().is_empty());
+}
+
+
+thread_local! {
+    static DROPPED: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Both hold their name for the drop log
+struct ConnectionPool(&'static str);
+struct SessionStore(&'static str);
+
+impl Injectable for ConnectionPool {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("pool")
+    }
+}
+
+impl Injectable for SessionStore {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("sessions")
+    }
+}
+
+impl Drop for ConnectionPool {
+    fn drop(&mut self) {
+        DROPPED.with(|dropped| dropped.borrow_mut().push(self.0));
+    }
+}
+
+impl Drop for SessionStore {
+    fn drop(&mut self) {
+        DROPPED.with(|dropped| dropped.borrow_mut().push(self.0));
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_drops_singletons_in_reverse_construction_order() {
+    let container = Container::new();
+    container.borrow::<ConnectionPool>();
+    let child = container.child();
+    child.resolve_ref::<SessionStore>();
+
+    drop(container);
+    assert!(DROPPED.with(|dropped| dropped.borrow().is_empty()), "the child still shares the cache");
+
+    drop(child);
+    assert_eq!(DROPPED.with(|dropped| dropped.take()), ["sessions", "pool"]);
+}
+
+
+struct Subscriber {
+    topics: Vec<&'static str>,
+}
+
+impl Injectable for Subscriber {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self { topics: Vec::new() }
+    }
+}
+
+impl PostConstruct for Subscriber {
+    fn post_construct(&mut self, container: &Container) {
+        self.topics.push("startup");
+        if let Some(topic) = container.resolve_named::<&'static str>("topic") {
+            self.topics.push(topic);
+        }
+    }
+}
+
+
+#[rstest]
+fn it_runs_post_construct_hooks() {
+    let container = Container::new();
+    container.register_named("topic", |_| "orders");
+
+    assert!(container.resolve::<Subscriber>().topics.is_empty());
+    assert_eq!(container.resolve_init::<Subscriber>().topics, ["startup", "orders"]);
+}
+
+
+#[rstest]
+fn it_resolves_bundles_of_services() {
+    let container = Container::new();
+    container.register_factory(|_| ForeignClient { base_url: "http://bundle".into() });
+
+    let (branch, client) = container.resolve::<Bundle<(Branch, Registered<ForeignClient>)>>().into_inner();
+
+    assert_eq!(branch.0.0, 1);
+    assert_eq!(client.base_url, "http://bundle");
+}
+
+
+/// Reached twice, through `Left` and through `Right`.
+struct Apex(usize);
+
+impl Injectable for Apex {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Transient;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(next_id())
+    }
+}
+
+struct Left(Rc<Apex>);
+struct Right(Rc<Apex>);
+struct Diamond(Left, Right);
+
+impl Injectable for Left {
+    type Deps = Rc<Apex>;
+
+    fn inject(apex: Self::Deps) -> Self {
+        Self(apex)
+    }
+}
+
+impl Injectable for Right {
+    type Deps = Rc<Apex>;
+
+    fn inject(apex: Self::Deps) -> Self {
+        Self(apex)
+    }
+}
+
+impl Injectable for Diamond {
+    type Deps = (Left, Right);
+
+    fn inject((left, right): Self::Deps) -> Self {
+        Self(left, right)
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_builds_a_shared_node_once_per_deduped_resolve() {
+    let container = Container::new();
+
+    let Diamond(Left(left), Right(right)) = container.resolve::<Diamond>();
+    assert!(!Rc::ptr_eq(&left, &right), "plain resolve builds per branch");
+
+    let Diamond(Left(first), Right(second)) = container.resolve_deduped::<Diamond>();
+    assert!(Rc::ptr_eq(&first, &second));
+
+    let Diamond(Left(next), _) = container.resolve_deduped::<Diamond>();
+    assert_ne!(next.0, first.0, "nothing outlives the call");
+    assert!(container.per_call.borrow().is_none());
+}
+
+
+/// Needs a `Pool` from whichever container has it registered.
+struct OrderStore(Registered<Pool>);
+
+impl Injectable for OrderStore {
+    type Deps = Registered<Pool>;
+
+    fn inject(pool: Self::Deps) -> Self {
+        Self(pool)
+    }
+}
+
+
+#[rstest]
+fn it_falls_back_to_the_parent_for_registrations() {
+    let parent = Rc::new(Container::new());
+    parent.register_instance(Pool("shared"));
+    parent.bind::<dyn Repository, PostgresRepository>(|imp| imp);
+
+    let module = Container::with_parent(parent.clone());
+    assert!(module.is_registered::<Pool>());
+    assert_eq!(module.resolve::<OrderStore>().0.0.0, "shared");
+    assert_eq!(module.resolve::<UserService>().repo.find(7), "pg-1-7");
+
+    // Local registrations shadow the parent's, and stay local
+    module.register_instance(Pool("local"));
+    assert_eq!(module.resolve::<OrderStore>().0.0.0, "local");
+    assert_eq!(parent.resolve_registered::<Pool>().unwrap().0, "shared");
+    assert!(!Container::new().is_registered::<Pool>());
+}
+
+
+type Command = fn(&str) -> String;
+
+/// Dispatches each command to the handler registered under its name.
+struct Router {
+    handlers: HashMap<String, Command>,
+}
+
+impl Injectable for Router {
+    type Deps = HashMap<String, Command>;
+
+    fn inject(handlers: Self::Deps) -> Self {
+        Self { handlers }
+    }
+}
+
+
+#[rstest]
+fn it_collects_named_registrations_into_a_map() {
+    let container = Container::new();
+    assert!(container.resolve_named_map::<Command>().is_empty());
+
+    container.register_named::<Command>("echo", |_| |input| input.to_string());
+    container.register_named::<Command>("upper", |_| |input| input.to_uppercase());
+    container.register_named::<Command>("reverse", |_| |input| input.chars().rev().collect());
+
+    let handlers = container.resolve_named_map::<Command>();
+    let mut names: Vec<_> = handlers.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["echo", "reverse", "upper"]);
+
+    let router = container.resolve::<Router>();
+    assert_eq!(router.handlers["upper"]("ok"), "OK");
+    assert_eq!(router.handlers["reverse"]("abc"), "cba");
+}
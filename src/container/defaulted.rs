@@ -0,0 +1,25 @@
+﻿use super::Injectable;
+
+
+/// A dependency on a type that isn't `Injectable` but is `Default`, usually
+/// a foreign one: `type Deps = (Defaulted<BTreeMap<String, u32>>, Repo);`.
+///
+/// A blanket `impl<A: Default> ResolveDepsFrom<_> for A` would overlap the
+/// one for `Injectable` types (a type may be both) and stable Rust has no
+/// specialization to pick between them, so the fallback is asked for by name.
+pub struct Defaulted<T>(pub T);
+
+dependency_wrapper!(Defaulted<T>, T => T, "Unwraps the value.");
+
+impl<T: Default> Injectable for Defaulted<T> {
+    type Deps = ();
+
+    #[inline(always)]
+    fn inject(_: Self::Deps) -> Self {
+        Defaulted(T::default())
+    }
+}
+
+
+#[cfg(test)]
+mod defaulted_test;
@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use rstest::*;
+use super::*;
+use super::super::Container;
+
+
+/// Stands in for a foreign type: `Default`, but not `Injectable`
+#[derive(Default)]
+struct RetryPolicy {
+    attempts: u8,
+}
+
+struct Client {
+    retries: Defaulted<RetryPolicy>,
+    headers: BTreeMap<String, String>,
+}
+
+impl Injectable for Client {
+    type Deps = (Defaulted<RetryPolicy>, Defaulted<BTreeMap<String, String>>);
+
+    fn inject((retries, headers): Self::Deps) -> Self {
+        Self { retries, headers: headers.into_inner() }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_default_types_as_dependencies() {
+    let container = Container::new();
+
+    let client = container.resolve::<Client>();
+    assert_eq!(client.retries.attempts, 0);
+    assert!(client.headers.is_empty());
+
+    let Defaulted(policy) = container.resolve::<Defaulted<RetryPolicy>>();
+    assert_eq!(policy.attempts, 0);
+}
@@ -0,0 +1,23 @@
+﻿use std::any::Any;
+
+
+/// Releases what a cached service holds (a file handle, a socket) once the
+/// cache it lives in goes away.
+///
+/// Opt in per type with
+/// [`Container::register_disposable`](super::Container::register_disposable);
+/// the container only ever disposes instances it owns, never transients.
+pub trait Disposable {
+    fn dispose(&mut self);
+}
+
+/// Type-erased call to `T::dispose`, one per registered type.
+pub(crate) fn dispose_as<T: Disposable + 'static>(instance: &mut dyn Any) {
+    if let Some(instance) = instance.downcast_mut::<T>() {
+        instance.dispose();
+    }
+}
+
+
+#[cfg(test)]
+mod disposable_test;
@@ -0,0 +1,94 @@
+use std::cell::RefCell;
+
+use rstest::*;
+use super::*;
+use super::super::{Container, Injectable, Scope};
+
+
+thread_local! {
+    /// What got disposed on this test thread, in order.
+    static DISPOSED: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+fn disposed() -> Vec<&'static str> {
+    DISPOSED.with(|log| log.borrow().clone())
+}
+
+struct Socket(&'static str);
+struct Session(&'static str);
+
+impl Injectable for Socket {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("socket")
+    }
+}
+
+impl Injectable for Session {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("session")
+    }
+}
+
+impl Disposable for Socket {
+    fn dispose(&mut self) {
+        DISPOSED.with(|log| log.borrow_mut().push(self.0));
+    }
+}
+
+impl Disposable for Session {
+    fn dispose(&mut self) {
+        DISPOSED.with(|log| log.borrow_mut().push(self.0));
+    }
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_disposes_singletons_when_the_container_drops() {
+    let container = Container::new();
+    container.register_disposable::<Socket>();
+    drop(container.resolve_ref::<Socket>());
+
+    let child = container.child();
+    drop(container);
+    assert!(disposed().is_empty(), "a child still shares the singleton");
+
+    drop(child);
+    assert_eq!(disposed(), ["socket"]);
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_disposes_scoped_instances_with_their_scope() {
+    let container = Container::new();
+    container.register_disposable::<Session>();
+
+    let request = container.child();
+    drop(request.resolve_ref::<Session>());
+    drop(container.resolve_ref::<Session>());
+    drop(request);
+    assert_eq!(disposed(), ["session"]);
+
+    drop(container);
+    assert_eq!(disposed(), ["session"], "the root caches no scoped instance");
+}
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_leaves_instances_shared_outside_alone() {
+    let container = Container::new();
+    container.register_disposable::<Socket>();
+
+    let socket = container.resolve_ref::<Socket>();
+    drop(container);
+
+    assert!(disposed().is_empty());
+    assert_eq!(socket.0, "socket");
+}
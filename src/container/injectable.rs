@@ -1,26 +1,56 @@
 ﻿
+#[cfg(feature = "derive")]
+pub use singularity_proc_macros::Injectable;
+
 /// Marks a type as constructible via DI.
 /// Must be implemented manually per service.
 ///
 /// Safety: Any recursive dependency will result in **compile-time failure**.
-
-#[cfg(feature = "derive")]
-pub use singularity_proc_macros::Injectable;
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not `Injectable`",
+    label = "not a service",
+    note = "services implement `Injectable`, any other value can be registered and taken as `Registered<{Self}>`"
+)]
 pub trait Injectable: Sized {
     type Deps;
     const SCOPE: super::scope::Scope = super::scope::Scope::Scoped;
+    /// Number of elements in `Deps`, for tooling to read at compile time. Set
+    /// by the derive; hand-written impls keep `0` unless they set it too.
+    const DEP_COUNT: usize = 0;
     fn inject(deps: Self::Deps) -> Self;
+
+    /// `inject` for [`Container::resolve_checked`](super::Container::resolve_checked),
+    /// which takes a construction error as [`ResolveError::ConstructionFailed`](super::ResolveError::ConstructionFailed).
+    /// Only the `panic-on-error` bridge for `TryInjectable` can fail here.
+    #[inline(always)]
+    fn inject_checked(deps: Self::Deps) -> Result<Self, super::ResolveError> {
+        Ok(Self::inject(deps))
+    }
+}
+
+/// Type markers resolve like any other dependency, there is nothing to build.
+impl<T: ?Sized> Injectable for core::marker::PhantomData<T> {
+    type Deps = ();
+    #[inline(always)]
+    fn inject(_: Self::Deps) -> Self {
+        core::marker::PhantomData
+    }
 }
 
 
 /// Macro for defining DI-ready structs with auto-generated `Injectable` implementations.
 /// (full docs below)
+///
+/// Attributes and doc comments written before a field or dependency are
+/// forwarded onto the generated struct field. Those written before the struct
+/// name land on the struct: `injectable!((a: A) => #[derive(Clone)] Service {})`
+/// (the unit struct arm derives `Copy` and `Clone` already).
 #[macro_export]
 macro_rules! injectable {
     // Unit struct — `injectable!(() => <vis>? <Name>)`
-    (() => $vis:vis $name:ident) => {
+    (() => $(#[$sattr:meta])* $vis:vis $name:ident) => {
         #[derive(Copy, Clone)]
-        $vis struct $name;
+        $(#[$sattr])* $vis struct $name;
 
         impl Injectable for $name {
             type Deps = ();
@@ -36,11 +66,11 @@ macro_rules! injectable {
 
     // Named struct, no dependencies —
     // `injectable!(() => <vis>? <Name> { <field>: <Type> = <expr>, ... })`
-    (() => $vis:vis $name:ident  {
-        $( $field:ident: $field_type:ty = $field_expr:expr ),* $(,)?
+    (() => $(#[$sattr:meta])* $vis:vis $name:ident  {
+        $( $(#[$fattr:meta])* $field:ident: $field_type:ty = $field_expr:expr ),* $(,)?
     }) => {
-        $vis struct $name {
-            $($field: $field_type),*
+        $(#[$sattr])* $vis struct $name {
+            $( $(#[$fattr])* $field: $field_type ),*
         }
 
         impl Injectable for $name {
@@ -58,11 +88,11 @@ macro_rules! injectable {
     // Tuple struct, no dependencies —
     // `injectable!(() => <vis>? <Name>(<Type> = <expr>, ...))`
     (
-        () => $vis:vis $name:ident  (
-            $( $field_type:ty = $field_expr:expr ),*  $(,)?
+        () => $(#[$sattr:meta])* $vis:vis $name:ident  (
+            $( $(#[$fattr:meta])* $field_type:ty = $field_expr:expr ),*  $(,)?
         )
     ) => {
-        $vis struct $name ($($field_type),*);
+        $(#[$sattr])* $vis struct $name ($( $(#[$fattr])* $field_type ),*);
 
         impl Injectable for $name {
             type Deps = ();
@@ -73,96 +103,226 @@ macro_rules! injectable {
         }
     };
 
+    // Named struct, defaulted dependencies —
+    // `injectable!((dep: Type = <expr>, ...) => <vis>? <Name> { <field>: <Type> = <expr>, ... })`
+    // Reads like a dep but nothing is resolved: each `<expr>` runs once per
+    // `inject`, e.g. to pin a `Box<dyn Trait>` to one implementation.
+    (
+        ($( $(#[$pattr:meta])* $param_name:ident : $param_type:ty = $param_expr:expr ),+ $(,)?) => $(#[$sattr:meta])* $vis:vis $name:ident {
+            $( $(#[$fattr:meta])* $field_name:ident: $field_type:ty = $field_expr:expr),*  $(,)?
+        }
+    ) => {
+        $crate::injectable!(@reject_self [$($param_name)+ $($field_name)*] {
+            $(#[$sattr])* $vis struct $name {
+                $( $(#[$pattr])* $param_name : $param_type, )+
+                $( $(#[$fattr])* $field_name : $field_type ),*
+            }
+
+            impl Injectable for $name {
+                type Deps = ();
+                #[inline(always)]
+                fn inject(_: Self::Deps) -> Self {
+                    $( let $param_name: $param_type = $param_expr; )+
+                    $( let $field_name: $field_type = $field_expr; )*
+                    Self {
+                        $( $param_name, )+
+                        $( $field_name ),*
+                    }
+                }
+            }
+        });
+    };
+
     // Named struct, one dependency —
     // `injectable!((dep: Type) => <vis>? <Name> { <field>: <Type> = <expr>, ... })`
     (
-        ($param_name:ident : $param_type:ty) => $vis:vis $name:ident {
-            $( $field_name:ident: $field_type:ty = $field_expr:expr),*  $(,)?
+        ($(#[$pattr:meta])* $param_name:ident : $param_type:ty) => $(#[$sattr:meta])* $vis:vis $name:ident {
+            $( $(#[$fattr:meta])* $field_name:ident: $field_type:ty = $field_expr:expr),*  $(,)?
         }
     ) => {
-        $vis struct $name {
-            $param_name : $param_type,
-            $( $field_name : $field_type ),*
-        }
+        $crate::injectable!(@reject_self [$param_name $($field_name)*] {
+            $(#[$sattr])* $vis struct $name {
+                $(#[$pattr])* $param_name : $param_type,
+                $( $(#[$fattr])* $field_name : $field_type ),*
+            }
 
-        impl Injectable for $name {
-            type Deps = $param_type;
-            #[inline(always)]
-            fn inject($param_name: Self::Deps) -> Self {
-                Self {
-                    $param_name,
-                    $( $field_name: $field_expr ),*
+            impl Injectable for $name {
+                type Deps = $param_type;
+                #[inline(always)]
+                fn inject($param_name: Self::Deps) -> Self {
+                    // Field exprs run before the dep moves in, so they may borrow it
+                    $( let $field_name: $field_type = $field_expr; )*
+                    Self {
+                        $param_name,
+                        $( $field_name ),*
+                    }
                 }
             }
-        }
+        });
     };
 
     // Tuple struct, one dependency —
     // `injectable!((dep: Type) => <vis>? <Name>(<Type> = <expr>, ...))`
     (
-        ($param_name:ident : $param_type:ty ) => $vis:vis $name:ident ($( $field_type:ty = $field_expr:expr ),* $(,)?)
+        ($(#[$pattr:meta])* $param_name:ident : $param_type:ty ) => $(#[$sattr:meta])* $vis:vis $name:ident ($( $(#[$fattr:meta])* $field_type:ty = $field_expr:expr ),* $(,)?)
     ) => {
-        $vis struct $name ($param_type, $($field_type),*);
+        $crate::injectable!(@reject_self [$param_name] {
+            $(#[$sattr])* $vis struct $name ($(#[$pattr])* $param_type, $( $(#[$fattr])* $field_type ),*);
 
-        impl Injectable for $name {
-            type Deps = $param_type;
-            #[inline(always)]
-            fn inject(deps: Self::Deps) -> Self {
-                Self (deps, $($field_expr),*)
+            impl Injectable for $name {
+                type Deps = $param_type;
+                #[inline(always)]
+                fn inject(deps: Self::Deps) -> Self {
+                    Self (deps, $($field_expr),*)
+                }
             }
-        }
+        });
     };
 
     // Named struct, multiple dependencies —
     // `injectable!((a:A, b:B, ...) => <vis>? <Name> { <field>: <Type> = <expr>, ... })`
     (
-       ( $f_param:ident : $f_type:ty , $( $r_param:ident : $r_type:ty),+ $(,)? ) => $vis:vis $name:ident {
-           $( $field_name:ident: $field_type:ty = $field_expr:expr),* $(,)?
+       ( $(#[$fattr0:meta])* $f_param:ident : $f_type:ty , $( $(#[$rattr:meta])* $r_param:ident : $r_type:ty),+ $(,)? ) => $(#[$sattr:meta])* $vis:vis $name:ident {
+           $( $(#[$fattr:meta])* $field_name:ident: $field_type:ty = $field_expr:expr),* $(,)?
        }
     ) => {
-        $vis struct $name {
-            $f_param: $f_type,
-            $($r_param: $r_type, ),+
-            $($field_name: $field_type,)*
-        }
+        $crate::injectable!(@reject_self [$f_param $($r_param)+ $($field_name)*] {
+            $(#[$sattr])* $vis struct $name {
+                $(#[$fattr0])* $f_param: $f_type,
+                $( $(#[$rattr])* $r_param: $r_type, ),+
+                $( $(#[$fattr])* $field_name: $field_type, )*
+            }
 
-        impl Injectable for $name {
-            type Deps =  ($f_type, $($r_type),+);
-            #[inline(always)]
-            fn inject(($f_param, $($r_param),+): Self::Deps) -> Self {
-                Self { $f_param, $($r_param),+ , $($field_name: $field_expr),* }
+            impl Injectable for $name {
+                type Deps =  ($f_type, $($r_type),+);
+                #[inline(always)]
+                fn inject(($f_param, $($r_param),+): Self::Deps) -> Self {
+                    // Field exprs run before the deps move in, so they may borrow them
+                    $( let $field_name: $field_type = $field_expr; )*
+                    Self { $f_param, $($r_param),+ , $($field_name),* }
+                }
             }
-        }
+        });
     };
 
 
     // Tuple struct, multiple dependencies —
     // `injectable!((a:A, b:B, ...) => <vis>? <Name>(<Type> = <expr>, ...))`
     (
-        ( $f_param:ident : $f_param_type:ty, $( $r_param:ident : $r_param_type:ty ),+ ) =>
-            $vis:vis $name:ident (
-                $( $field_type:ty = $field_expr:expr ),* $(,)?
+        ( $(#[$fattr0:meta])* $f_param:ident : $f_param_type:ty, $( $(#[$rattr:meta])* $r_param:ident : $r_param_type:ty ),+ ) =>
+            $(#[$sattr:meta])* $vis:vis $name:ident (
+                $( $(#[$fattr:meta])* $field_type:ty = $field_expr:expr ),* $(,)?
             )
     ) => {
-        $vis struct $name (
-            $f_param_type,
-            $( $r_param_type ),+,
-            $( $field_type ),*
-        );
+        $crate::injectable!(@reject_self [$f_param $($r_param)+] {
+            $(#[$sattr])* $vis struct $name (
+                $(#[$fattr0])* $f_param_type,
+                $( $(#[$rattr])* $r_param_type ),+,
+                $( $(#[$fattr])* $field_type ),*
+            );
+
+            impl Injectable for $name {
+                type Deps = ($f_param_type, $( $r_param_type ),+);
+
+                #[inline(always)]
+                fn inject(($f_param, $($r_param),+): Self::Deps) -> Self {
+
+                    Self(
+                        $f_param,
+                        $($r_param),+,
+                        $( $field_expr ),*)
+                }
+            }
+        });
+    };
+
+    // Tuple struct, dependencies declared in place —
+    // `injectable!((..) => <vis>? <Name>(<Type> = <expr> | <dep>: <Type>, ...))`
+    // Field order is kept exactly as written, so deps may follow plain fields.
+    (
+        (..) => $(#[$sattr:meta])* $vis:vis $name:ident ( $($body:tt)* )
+    ) => {
+        $crate::injectable!(@tuple [$(#[$sattr])* $vis $name] [] [] [] $($body)*);
+    };
+
+    // region tuple muncher
+    // Dependency field: `<dep>: <Type>`
+    (@tuple [$(#[$sattr:meta])* $vis:vis $name:ident] [$($ft:tt)*] [$($dep:tt)*] [$($init:tt)*]
+        $(#[$fattr:meta])* $param:ident : $param_type:ty $(, $($rest:tt)*)?
+    ) => {
+        $crate::injectable!(@reject_self [$param] {
+            $crate::injectable!(@tuple [$(#[$sattr])* $vis $name]
+                [$($ft)* $(#[$fattr])* $param_type,]
+                [$($dep)* $param: $param_type,]
+                [$($init)* $param,]
+                $($($rest)*)?);
+        });
+    };
+
+    // Plain field: `<Type> = <expr>`
+    (@tuple [$(#[$sattr:meta])* $vis:vis $name:ident] [$($ft:tt)*] [$($dep:tt)*] [$($init:tt)*]
+        $(#[$fattr:meta])* $field_type:ty = $field_expr:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::injectable!(@tuple [$(#[$sattr])* $vis $name]
+            [$($ft)* $(#[$fattr])* $field_type,]
+            [$($dep)*]
+            [$($init)* $field_expr,]
+            $($($rest)*)?);
+    };
+
+    // Done, no dependencies
+    (@tuple [$(#[$sattr:meta])* $vis:vis $name:ident] [$($ft:tt)*] [] [$($init:expr,)*]) => {
+        $(#[$sattr])* $vis struct $name ($($ft)*);
 
         impl Injectable for $name {
-            type Deps = ($f_param_type, $( $r_param_type ),+);
+            type Deps = ();
+            #[inline(always)]
+            fn inject(_: Self::Deps) -> Self {
+                Self ($($init),*)
+            }
+        }
+    };
 
+    // Done, one dependency
+    (@tuple [$(#[$sattr:meta])* $vis:vis $name:ident] [$($ft:tt)*] [$param:ident: $param_type:ty,] [$($init:expr,)*]) => {
+        $(#[$sattr])* $vis struct $name ($($ft)*);
+
+        impl Injectable for $name {
+            type Deps = $param_type;
             #[inline(always)]
-            fn inject(($f_param, $($r_param),+): Self::Deps) -> Self {
+            fn inject($param: Self::Deps) -> Self {
+                Self ($($init),*)
+            }
+        }
+    };
 
-                Self(
-                    $f_param,
-                    $($r_param),+,
-                    $( $field_expr ),*)
+    // Done, multiple dependencies
+    (@tuple [$(#[$sattr:meta])* $vis:vis $name:ident] [$($ft:tt)*] [$($param:ident: $param_type:ty,)+] [$($init:expr,)*]) => {
+        $(#[$sattr])* $vis struct $name ($($ft)*);
+
+        impl Injectable for $name {
+            type Deps = ($($param_type),+);
+            #[inline(always)]
+            fn inject(($($param),+): Self::Deps) -> Self {
+                Self ($($init),*)
             }
         }
     };
+    // endregion
+
+    // region param check
+    // Params double as field names and bindings, so a keyword can't be one;
+    // `self` is the one `ident` lets through, so expand only once it's ruled out
+    (@reject_self [] { $($expansion:tt)* }) => {
+        $($expansion)*
+    };
+    (@reject_self [self $($rest:tt)*] $expansion:tt) => {
+        compile_error!("`self` can't name an `injectable!` dependency or field");
+    };
+    (@reject_self [$param:tt $($rest:tt)*] $expansion:tt) => {
+        $crate::injectable!(@reject_self [$($rest)*] $expansion);
+    };
+    // endregion
 
 }
 
@@ -90,4 +90,197 @@ fn it_should_create_service_with_macro() {
     assert_eq!(s4.x, 5);
     assert_eq!(s4.a.0, 7);
     assert_eq!(s4.b.0, 8);
-}
\ No newline at end of file
+}
+
+injectable!((..) => PlainDepPlain(i32 = 1, d: Dummy2, u8 = 3));
+injectable!((..) => DepPlainDep(a: Dummy2, &'static str = "mid", b: Dummy2));
+
+
+#[rstest]
+fn it_should_keep_declaration_order_for_mixed_tuple_macro() {
+    // (plain, dep, plain)
+    let s1 = PlainDepPlain::inject(Dummy2(10));
+    assert_eq!(s1.0, 1);
+    assert_eq!(s1.1.0, 10);
+    assert_eq!(s1.2, 3);
+
+    // (dep, plain, dep)
+    let s2 = DepPlainDep::inject((Dummy2(7), Dummy2(8)));
+    assert_eq!(s2.0.0, 7);
+    assert_eq!(s2.1, "mid");
+    assert_eq!(s2.2.0, 8);
+
+    // Resolved through the container as well
+    let container = Container::new();
+    let s3 = container.resolve::<PlainDepPlain>();
+    assert_eq!(s3.1.0, 10);
+}
+
+
+injectable!((d: Dummy2) => Doubled { doubled: i32 = d.0 * 2 });
+injectable!((a: Dummy2, b: Dummy2) => Summed { total: i32 = a.0 + b.0, label: String = format!("{}+{}", a.0, b.0) });
+
+
+#[rstest]
+fn it_should_compute_macro_fields_from_injected_dependencies() {
+    let doubled = Doubled::inject(Dummy2(21));
+    assert_eq!(doubled.d.0, 21);
+    assert_eq!(doubled.doubled, 42);
+
+    let summed = Summed::inject((Dummy2(7), Dummy2(8)));
+    assert_eq!(summed.total, 15);
+    assert_eq!(summed.label, "7+8");
+    assert_eq!(summed.a.0 + summed.b.0, 15);
+
+    // Through the container
+    assert_eq!(Container::new().resolve::<Doubled>().doubled, 20);
+}
+
+
+// Every field here is write-only: only the forwarded `#[allow(dead_code)]`
+// keeps the lint quiet, so dropping an attribute fails
+// `cargo clippy -- -D warnings`.
+injectable!((
+    /// Resolved first
+    #[allow(dead_code)] first: Dummy2,
+    #[allow(dead_code)] second: Dummy2,
+) => Annotated {
+    /// Plain field with docs
+    #[allow(dead_code)]
+    retries: u8 = 3,
+});
+injectable!((..) => AnnotatedTuple(
+    /// Dependency
+    #[allow(dead_code)] d: Dummy2,
+    #[allow(dead_code)] u8 = 1,
+));
+injectable!(() => AnnotatedPlain {
+    #[allow(dead_code)]
+    /// Attributes and docs in any order
+    value: i32 = 4,
+});
+
+
+#[rstest]
+fn it_should_forward_field_attributes_in_macro() {
+    let container = Container::new();
+
+    let _: Annotated = container.resolve();
+    let _: AnnotatedTuple = container.resolve();
+    let _: AnnotatedPlain = container.resolve();
+}
+
+
+trait Repo {
+    fn table(&self) -> &str;
+}
+
+struct SqlRepo;
+
+impl Repo for SqlRepo {
+    fn table(&self) -> &str {
+        "users"
+    }
+}
+
+injectable!((repo: Box<dyn Repo> = Box::new(SqlRepo)) => DefaultedRepoService {
+    table: String = repo.table().to_uppercase(),
+});
+
+
+#[rstest]
+fn it_should_build_defaulted_trait_object_dependencies() {
+    let service: DefaultedRepoService = Container::new().resolve();
+
+    assert_eq!(service.repo.table(), "users");
+    assert_eq!(service.table, "USERS");
+}
+
+
+// Checked at compile time, a broken graph fails the build
+const _: () = crate::testing::assert_resolves::<Dummy>();
+crate::assert_injectable!(Dummy2, OneDepWithField, ClashingNames);
+
+
+#[rstest]
+fn it_resolves_through_the_testing_harness() {
+    crate::testing::assert_resolves::<Dummy2>();
+
+    let dummy: Dummy = crate::testing::resolve_for_test();
+    assert_eq!(dummy.0.0, 10);
+}
+
+
+// Params named after the generated fn and its argument stay distinct bindings
+injectable!((inject: Dummy2, deps: Dummy2) => ClashingNames { total: i32 = inject.0 + deps.0 });
+injectable!((deps: Dummy2) => ClashingTuple(u8 = 3));
+injectable!((..) => ClashingMixed(inject: Dummy2, i32 = 1));
+
+
+#[rstest]
+fn it_should_accept_params_named_like_macro_internals() {
+    let container = Container::new();
+
+    let named: ClashingNames = container.resolve();
+    assert_eq!(named.total, 20);
+    assert_eq!(named.inject.0 + named.deps.0, 20);
+
+    let tuple: ClashingTuple = container.resolve();
+    assert_eq!((tuple.0.0, tuple.1), (10, 3));
+
+    let mixed: ClashingMixed = container.resolve();
+    assert_eq!((mixed.0.0, mixed.1), (10, 1));
+}
+
+
+/// Typed by `T` alone, the marker is its only dependency
+struct Codec<T: ?Sized> {
+    marker: std::marker::PhantomData<T>,
+    version: u8,
+}
+
+impl<T: ?Sized + 'static> Injectable for Codec<T> {
+    type Deps = (std::marker::PhantomData<T>, Dummy2);
+
+    fn inject((marker, dummy): Self::Deps) -> Self {
+        Self { marker, version: dummy.0 as u8 }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_phantom_data_dependencies() {
+    let codec: Codec<str> = Container::new().resolve();
+
+    assert_eq!(codec.version, 10);
+    assert_eq!(codec.marker, std::marker::PhantomData::<str>);
+    crate::testing::assert_resolves::<std::marker::PhantomData<dyn Fn()>>();
+}
+
+
+#[derive(Clone)]
+struct Port(u16);
+
+impl Injectable for Port {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(8080)
+    }
+}
+
+injectable!((port: Port) => #[derive(Clone)] ClonedListener { label: String = format!("port {}", port.0) });
+injectable!((..) => #[derive(Clone)] ClonedTuple(port: Port, u8 = 3));
+
+
+#[rstest]
+fn it_forwards_struct_attributes_like_derives() {
+    let container = Container::new();
+
+    let listener: ClonedListener = container.resolve();
+    let copy = listener.clone();
+    assert_eq!((copy.port.0, copy.label.as_str()), (8080, "port 8080"));
+
+    let tuple = container.resolve::<ClonedTuple>().clone();
+    assert_eq!((tuple.0.0, tuple.1), (8080, 3));
+}
@@ -0,0 +1,62 @@
+﻿use std::cell::OnceCell;
+use std::ops::Deref;
+
+use super::resolve_deps_from::ResolveDepsFrom;
+use super::{Container, Injectable};
+
+
+/// A dependency resolved on first access rather than up front.
+///
+/// Resolving `LazyDep<T>` asks nothing of `T`'s deps, so it breaks a cycle
+/// the compiler would otherwise reject: `A` may hold `LazyDep<B>` while `B`
+/// holds `LazyDep<A>`. The first [`get`](LazyDep::get) (or deref) resolves
+/// `T` from the container the handle came from, later ones reuse it.
+///
+/// The handle keeps that container's registries and caches alive; a lazy
+/// dep stored inside a singleton of its own container is a reference cycle.
+pub struct LazyDep<T> {
+    container: Container,
+    /// Boxed, or two types holding lazy deps on each other would be infinitely sized.
+    value: OnceCell<Box<T>>,
+}
+
+impl<T> LazyDep<T>
+where
+    T: Injectable,
+    T::Deps: ResolveDepsFrom<Container>,
+{
+    /// Resolves `T` the first time, then hands out the same one.
+    pub fn get(&self) -> &T {
+        self.value.get_or_init(|| Box::new(self.container.resolve::<T>()))
+    }
+
+    /// Unwraps the value, resolving it if nobody asked yet.
+    pub fn into_inner(self) -> T {
+        let Self { container, value } = self;
+        value.into_inner().map_or_else(|| container.resolve::<T>(), |value| *value)
+    }
+}
+
+impl<T> Deref for LazyDep<T>
+where
+    T: Injectable,
+    T::Deps: ResolveDepsFrom<Container>,
+{
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        self.get()
+    }
+}
+
+/// Only a handle onto the container, `T` isn't touched until first access.
+impl<T> ResolveDepsFrom<Container> for LazyDep<T> {
+    fn resolve_deps(container: &Container) -> Self {
+        LazyDep { container: container.handle(), value: OnceCell::new() }
+    }
+}
+
+
+#[cfg(test)]
+mod lazy_dep_test;
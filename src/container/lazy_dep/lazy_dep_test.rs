@@ -0,0 +1,62 @@
+use std::cell::Cell;
+
+use rstest::*;
+use super::*;
+
+
+thread_local! {
+    static RESOLVED: Cell<u32> = const { Cell::new(0) };
+}
+
+/// One side of a cycle: each holds a lazy handle on the other
+struct Parent {
+    name: &'static str,
+    child: LazyDep<Child>,
+}
+
+struct Child {
+    name: &'static str,
+    parent: LazyDep<Parent>,
+}
+
+impl Injectable for Parent {
+    type Deps = LazyDep<Child>;
+
+    fn inject(child: Self::Deps) -> Self {
+        RESOLVED.with(|resolved| resolved.set(resolved.get() + 1));
+        Self { name: "parent", child }
+    }
+}
+
+impl Injectable for Child {
+    type Deps = LazyDep<Parent>;
+
+    fn inject(parent: Self::Deps) -> Self {
+        RESOLVED.with(|resolved| resolved.set(resolved.get() + 1));
+        Self { name: "child", parent }
+    }
+}
+
+
+#[rstest]
+fn it_breaks_a_mutual_dependency_cycle() {
+    let parent = Container::new().resolve::<Parent>();
+    assert_eq!(RESOLVED.with(Cell::get), 1, "the child waits for first access");
+
+    assert_eq!(parent.child.get().name, "child");
+    assert_eq!(parent.child.parent.name, "parent");
+    assert_eq!(parent.name, "parent");
+    assert_eq!(RESOLVED.with(Cell::get), 3);
+
+    // Resolved once, then reused
+    let _ = parent.child.get();
+    assert_eq!(RESOLVED.with(Cell::get), 3);
+}
+
+
+#[rstest]
+fn it_resolves_on_into_inner() {
+    let child = Container::new().resolve::<Parent>().child.into_inner();
+
+    assert_eq!(child.name, "child");
+}
@@ -0,0 +1,8 @@
+﻿/// Second construction phase, run once dependencies are wired in, e.g. to
+/// subscribe to an event bus.
+///
+/// Only [`Container::resolve_init`](super::Container::resolve_init) calls it,
+/// plain `resolve` never does.
+pub trait PostConstruct: super::Injectable {
+    fn post_construct(&mut self, container: &super::Container);
+}
@@ -0,0 +1,120 @@
+﻿use std::collections::HashMap;
+
+use super::resolve_deps_from::ResolveDepsFrom;
+
+
+/// A dependency built by a factory registered at runtime.
+///
+/// Use it for types that can't implement `Injectable`:
+/// `type Deps = Registered<reqwest::Client>;`
+///
+/// Panics during resolution if no factory was registered for `T`.
+pub struct Registered<T>(pub T);
+
+dependency_wrapper!(Registered<T>, T => T, "Unwraps the registered value.");
+
+
+/// Consults the runtime factory registry.
+impl<T: 'static> ResolveDepsFrom<super::Container> for Registered<T> {
+    fn resolve_deps(container: &super::Container) -> Self {
+        Self::try_resolve_deps(container).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    fn try_resolve_deps(container: &super::Container) -> Result<Self, super::ResolveError> {
+        container.resolve_registered::<T>().map(Registered).ok_or_else(super::ResolveError::missing_factory::<T>)
+    }
+}
+
+
+/// Every `T` registered by name, keyed by it; empty when there are none.
+/// See [`Container::resolve_named_map`](super::Container::resolve_named_map).
+impl<T: 'static> ResolveDepsFrom<super::Container> for HashMap<String, T> {
+    fn resolve_deps(container: &super::Container) -> Self {
+        container.resolve_named_map::<T>()
+    }
+}
+
+
+/// `None` instead of a panic when no factory is registered.
+impl<T: 'static> ResolveDepsFrom<super::Container> for Option<Registered<T>> {
+    fn resolve_deps(container: &super::Container) -> Self {
+        container.resolve_registered::<T>().map(Registered)
+    }
+}
+
+
+/// An `Injectable` dependency that a registration can stand in for: the
+/// registered instance (or factory result) if there is one, else a freshly
+/// resolved `T`.
+///
+/// Mixes freely with other deps, `type Deps = (Preferred<Config>, Cache);`
+/// takes `Config` from the registry when registered and always builds `Cache`.
+/// Plain `T` deps never look at the registry, that would cost every resolve a
+/// lookup and require `T: 'static`.
+pub struct Preferred<T>(pub T);
+
+dependency_wrapper!(Preferred<T>, T => T, "Unwraps the value.");
+
+/// Registry first, `Injectable` as the fallback.
+impl<T> ResolveDepsFrom<super::Container> for Preferred<T>
+where
+    T: super::Injectable + 'static,
+    T::Deps: ResolveDepsFrom<super::Container>,
+{
+    fn resolve_deps(container: &super::Container) -> Self {
+        Preferred(container.resolve_registered::<T>().unwrap_or_else(|| container.resolve::<T>()))
+    }
+
+    fn try_resolve_deps(container: &super::Container) -> Result<Self, super::ResolveError> {
+        match container.resolve_registered::<T>() {
+            Some(value) => Ok(Preferred(value)),
+            None => container.resolve_checked::<T>().map(Preferred),
+        }
+    }
+}
+
+
+/// Registers already constructed values, one `register_instance` call each.
+///
+/// ```
+/// # use std::rc::Rc;
+/// # use singularity::container::*;
+/// # use singularity::register;
+/// # trait Logger {}
+/// # struct StdoutLogger;
+/// # impl Logger for StdoutLogger {}
+/// # #[derive(Clone)]
+/// # struct DbPool;
+/// # #[derive(Clone)]
+/// # struct Cache;
+/// # let container = Container::new();
+/// # let (db_pool, cache, logger) = (DbPool, Cache, Rc::new(StdoutLogger));
+/// register!(container, db_pool, cache, logger as Rc<dyn Logger>,);
+/// # assert!(container.is_registered::<DbPool>() && container.is_registered::<Rc<dyn Logger>>());
+/// ```
+///
+/// `value as Type` registers under `Type`, coercing first (e.g. to a trait object);
+/// it takes a plain identifier, bind more complex expressions to a local first.
+#[macro_export]
+macro_rules! register {
+    ($container:expr $(, $($values:tt)*)?) => {{
+        let container = &$container;
+        $( $crate::register!(@each container, $($values)*); )?
+    }};
+
+    (@each $container:ident, $value:ident as $ty:ty $(, $($rest:tt)*)?) => {
+        let value: $ty = $value;
+        $container.register_instance::<$ty>(value);
+        $( $crate::register!(@each $container, $($rest)*); )?
+    };
+
+    (@each $container:ident, $value:expr $(, $($rest:tt)*)?) => {
+        $container.register_instance($value);
+        $( $crate::register!(@each $container, $($rest)*); )?
+    };
+
+    (@each $container:ident,) => {};
+    (@each $container:ident) => {};
+}
+
+pub use register;
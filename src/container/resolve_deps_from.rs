@@ -1,52 +1,256 @@
-﻿
+﻿use std::borrow::Cow;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use super::{ResolveError, Resolver};
 
 
 /// A general contract for resolving dependency tuples.
-/// Implemented up to 8 levels manually for performance and control.
+/// Implemented up to 16 levels manually for performance and control.
+///
+/// Everything built purely from `resolve` (tuples, `Option`, arrays, ...)
+/// works with any [`Resolver`]; registry-backed deps need a `Container`.
 ///
 /// Recursive resolution will emit a compile-time error instead of runtime failure.
 pub trait ResolveDepsFrom<C>: Sized {
     fn resolve_deps(container: &C) -> Self;
+
+    /// `resolve_deps` for [`Container::resolve_checked`](super::Container::resolve_checked):
+    /// a registry miss is an `Err` rather than a panic. Deps that can't miss
+    /// keep the default.
+    #[inline(always)]
+    fn try_resolve_deps(container: &C) -> Result<Self, ResolveError> {
+        Ok(Self::resolve_deps(container))
+    }
 }
 
 
 /// Base case: service has no dependencies.
-impl ResolveDepsFrom<super::Container> for () {
+impl<R: Resolver> ResolveDepsFrom<R> for () {
     #[inline(always)]
-    fn resolve_deps(_: &super::Container) -> Self {
-        ()
-    }
+    fn resolve_deps(_: &R) -> Self {}
 }
 
 /// Automatically resolves a single dependency.
-impl<A> ResolveDepsFrom<super::Container> for A
+impl<R: Resolver, A> ResolveDepsFrom<R> for A
+where
+    A: super::Injectable,
+    A::Deps: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        container.resolve::<A>()
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        container.resolve_checked::<A>()
+    }
+}
+
+
+/// Optional collaborator. An `Injectable` is always resolvable, so this is
+/// always `Some`; see `Option<Registered<T>>` for the registry-backed variant.
+impl<R: Resolver, A> ResolveDepsFrom<R> for Option<A>
 where
     A: super::Injectable,
+    A::Deps: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        Some(container.resolve::<A>())
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        container.resolve_checked::<A>().map(Some)
+    }
+}
+
+
+/// For services that keep their own `Result`: resolution can't fail here,
+/// so this is always `Ok`, whatever `E` is.
+impl<R: Resolver, A, E> ResolveDepsFrom<R> for Result<A, E>
+where
+    A: super::Injectable,
+    A::Deps: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        Ok(container.resolve::<A>())
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        container.resolve_checked::<A>().map(Ok)
+    }
+}
+
+
+/// Always `Cow::Owned`, there is nothing to borrow from.
+///
+/// Bounded on `B::Owned` rather than `B`, so `Cow<'static, A>` works for any
+/// `A: Injectable + Clone` (via the blanket `ToOwned`), and `Cow<'static, str>`
+/// whenever `String` is injectable (see the `std-defaults` feature).
+impl<R: Resolver, B> ResolveDepsFrom<R> for Cow<'static, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: super::Injectable,
+    <B::Owned as super::Injectable>::Deps: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        Cow::Owned(container.resolve::<B::Owned>())
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        container.resolve_checked::<B::Owned>().map(Cow::Owned)
+    }
+}
+
+
+/// Shared mutable state: wraps a freshly resolved `A`, ready to clone across threads.
+impl<R: Resolver, A> ResolveDepsFrom<R> for Arc<Mutex<A>>
+where
+    A: super::Injectable,
+    A::Deps: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        Arc::new(Mutex::new(container.resolve::<A>()))
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        container.resolve_checked::<A>().map(|value| Arc::new(Mutex::new(value)))
+    }
+}
+
+/// Single-threaded counterpart of `Arc<Mutex<A>>`.
+impl<R: Resolver, A> ResolveDepsFrom<R> for Rc<RefCell<A>>
+where
+    A: super::Injectable,
+    A::Deps: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        Rc::new(RefCell::new(container.resolve::<A>()))
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        container.resolve_checked::<A>().map(|value| Rc::new(RefCell::new(value)))
+    }
+}
+
+/// Shared instance, through `Container::resolve_ref`: a singleton `A` comes
+/// straight from the cache, so every `Rc<A>` in the graph is the same one.
+/// [`Cloned<A>`](super::Cloned) is an owned copy of that same instance.
+impl<A> ResolveDepsFrom<super::Container> for Rc<A>
+where
+    A: super::Injectable + 'static,
     A::Deps: ResolveDepsFrom<super::Container>,
 {
     #[inline(always)]
     fn resolve_deps(container: &super::Container) -> Self {
-        container.resolve::<A>()
+        container.resolve_ref::<A>()
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &super::Container) -> Result<Self, ResolveError> {
+        container.resolve_ref_checked::<A>()
+    }
+}
+
+/// The container itself, for services that resolve later on (a service
+/// locator, so only where asked for by name). It's another handle onto the
+/// same registries and caches; held by a cached singleton it's a reference cycle.
+impl ResolveDepsFrom<super::Container> for Rc<super::Container> {
+    #[inline(always)]
+    fn resolve_deps(container: &super::Container) -> Self {
+        Rc::new(container.handle())
+    }
+}
+
+
+/// Resolves `N` independently constructed instances, e.g. `[Worker; 4]`.
+/// `array::from_fn` fills the array without requiring `Copy`.
+impl<R: Resolver, A, const N: usize> ResolveDepsFrom<R> for [A; N]
+where
+    A: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        std::array::from_fn(|_| A::resolve_deps(container))
     }
+
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        let deps = (0..N).map(|_| A::try_resolve_deps(container)).collect::<Result<Vec<A>, _>>()?;
+        Ok(deps.try_into().unwrap_or_else(|_| unreachable!("collected exactly `N` deps")))
+    }
+}
+
+
+mod sealed {
+    pub trait Sealed {}
 }
 
+/// Marks the tuples used as `Deps` lists: `()`, `(A,)`, `(A, B)`, … up to 16.
+///
+/// Tuples are never services themselves, resolve their elements instead (see
+/// `resolve_all!`). Sealed, so it can't be implemented outside this crate.
+pub trait DepsTuple: sealed::Sealed {}
 
+impl sealed::Sealed for () {}
+impl DepsTuple for () {}
 
 
+/// One-element tuple, as produced by macro expansions over a single dep.
+/// `(A,)` is a tuple type, not `A`, so this can't overlap the blanket impl.
+impl<R: Resolver, A> ResolveDepsFrom<R> for (A,)
+where
+    A: ResolveDepsFrom<R>,
+{
+    #[inline(always)]
+    fn resolve_deps(container: &R) -> Self {
+        (A::resolve_deps(container),)
+    }
+
+    #[inline(always)]
+    fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+        Ok((A::try_resolve_deps(container)?,))
+    }
+}
+
+impl<A> sealed::Sealed for (A,) {}
+impl<A> DepsTuple for (A,) {}
+
+
+/// Tuple elements only need to be resolvable themselves, not `Injectable`,
+/// so nested tuples like `((A, B), C)` compose recursively.
 macro_rules! resolve_deps_from {
     (
       $( $T:ident),+
     ) => {
-        impl<$($T),+> ResolveDepsFrom<super::Container> for ($($T),+)
+        impl<R: Resolver, $($T),+> ResolveDepsFrom<R> for ($($T),+)
             where
-                $($T: super::Injectable),+,
-                $($T::Deps:  ResolveDepsFrom<super::Container>),+
+                $($T: ResolveDepsFrom<R>),+
         {
             #[inline(always)]
-            fn resolve_deps(container: &super::Container) -> Self {
-                ($(container.resolve::<$T>()),+)
+            fn resolve_deps(container: &R) -> Self {
+                ($($T::resolve_deps(container)),+)
+            }
+
+            #[inline(always)]
+            fn try_resolve_deps(container: &R) -> Result<Self, ResolveError> {
+                Ok(($($T::try_resolve_deps(container)?),+))
             }
         }
+
+        impl<$($T),+> sealed::Sealed for ($($T),+) {}
+        impl<$($T),+> DepsTuple for ($($T),+) {}
     };
 }
 
@@ -67,4 +271,8 @@ resolve_deps_from!(A, B, C, D, E, F, G, H, I, J, K, L);
 resolve_deps_from!(A, B, C, D, E, F, G, H, I, J, K, L, M);
 resolve_deps_from!(A, B, C, D, E, F, G, H, I, J, K, L, M, N);
 resolve_deps_from!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
-resolve_deps_from!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
\ No newline at end of file
+resolve_deps_from!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+
+
+#[cfg(test)]
+mod resolve_deps_from_test;
@@ -0,0 +1,271 @@
+﻿
+
+use rstest::*;
+use super::*;
+use super::super::{Cloned, Container, Injectable};
+
+
+struct A(i32);
+struct B(i32);
+struct C(i32);
+
+impl Injectable for A {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(1)
+    }
+}
+
+impl Injectable for B {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(2)
+    }
+}
+
+impl Injectable for C {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(3)
+    }
+}
+
+
+struct Composed {
+    sub: (A, B),
+    c: C,
+}
+
+impl Injectable for Composed {
+    type Deps = ((A, B), C);
+
+    fn inject((sub, c): Self::Deps) -> Self {
+        Self { sub, c }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_nested_tuple_dependencies() {
+    let container = Container::new();
+
+    let ((a, b), c) = <((A, B), C)>::resolve_deps(&container);
+    assert_eq!((a.0, b.0, c.0), (1, 2, 3));
+
+    let svc = container.resolve::<Composed>();
+    assert_eq!(svc.sub.0.0, 1);
+    assert_eq!(svc.sub.1.0, 2);
+    assert_eq!(svc.c.0, 3);
+}
+
+
+static NEXT_COUNTER: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Not `Copy`, every instance carries its own id.
+struct Counter(usize);
+
+impl Injectable for Counter {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(NEXT_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+    }
+}
+
+struct Pool {
+    workers: [Counter; 3],
+}
+
+impl Injectable for Pool {
+    type Deps = [Counter; 3];
+
+    fn inject(workers: Self::Deps) -> Self {
+        Self { workers }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_homogeneous_array_dependencies() {
+    let container = Container::new();
+
+    let pool = container.resolve::<Pool>();
+    let [a, b, c] = &pool.workers;
+
+    assert_ne!(a.0, b.0);
+    assert_ne!(b.0, c.0);
+    assert_ne!(a.0, c.0);
+}
+
+
+struct Single {
+    a: A,
+}
+
+impl Injectable for Single {
+    type Deps = (A,);
+
+    fn inject((a,): Self::Deps) -> Self {
+        Self { a }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_single_element_tuples() {
+    let container = Container::new();
+
+    assert_eq!(container.resolve::<Single>().a.0, 1);
+    assert_eq!(<(A,)>::resolve_deps(&container).0.0, 1);
+}
+
+
+#[derive(Clone)]
+struct Label(&'static str);
+
+impl Injectable for Label {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("primary")
+    }
+}
+
+struct Tagger {
+    label: Cow<'static, Label>,
+}
+
+impl Injectable for Tagger {
+    type Deps = Cow<'static, Label>;
+
+    fn inject(label: Self::Deps) -> Self {
+        Self { label }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_cow_dependencies_as_owned() {
+    let tagger = Container::new().resolve::<Tagger>();
+
+    assert!(matches!(tagger.label, Cow::Owned(Label("primary"))));
+}
+
+
+#[cfg(feature = "std-defaults")]
+#[rstest]
+fn it_resolves_cow_str_through_string() {
+    let name = <Cow<'static, str>>::resolve_deps(&Container::new());
+
+    assert!(matches!(name, Cow::Owned(ref owned) if owned.is_empty()));
+}
+
+
+struct Tally {
+    shared: std::sync::Arc<std::sync::Mutex<Counter>>,
+    local: std::rc::Rc<std::cell::RefCell<Counter>>,
+}
+
+impl Injectable for Tally {
+    type Deps = (std::sync::Arc<std::sync::Mutex<Counter>>, std::rc::Rc<std::cell::RefCell<Counter>>);
+
+    fn inject((shared, local): Self::Deps) -> Self {
+        Self { shared, local }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_shared_mutable_dependencies() {
+    let tally = Container::new().resolve::<Tally>();
+
+    let handle = tally.shared.clone();
+    let start = tally.shared.lock().unwrap().0;
+    std::thread::spawn(move || handle.lock().unwrap().0 += 10).join().unwrap();
+    assert_eq!(tally.shared.lock().unwrap().0, start + 10);
+
+    let alias = tally.local.clone();
+    alias.borrow_mut().0 = 99;
+    assert_eq!(tally.local.borrow().0, 99);
+}
+
+
+static CONFIGS_BUILT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct AppConfig {
+    name: String,
+}
+
+impl Injectable for AppConfig {
+    type Deps = ();
+    const SCOPE: super::super::Scope = super::super::Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        CONFIGS_BUILT.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Self { name: "app".into() }
+    }
+}
+
+/// Shares the singleton
+struct Reader(Rc<AppConfig>);
+/// Keeps its own copy of it
+struct Editor(Cloned<AppConfig>);
+
+impl Injectable for Reader {
+    type Deps = Rc<AppConfig>;
+
+    fn inject(config: Self::Deps) -> Self {
+        Self(config)
+    }
+}
+
+impl Injectable for Editor {
+    type Deps = Cloned<AppConfig>;
+
+    fn inject(config: Self::Deps) -> Self {
+        Self(config)
+    }
+}
+
+
+#[rstest]
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+fn it_resolves_rc_and_cloned_dependencies_from_the_singleton_cache() {
+    let container = Container::new();
+
+    let (reader, mut editor) = <(Reader, Editor)>::resolve_deps(&container);
+    editor.0.name.push_str("-draft");
+
+    assert!(Rc::ptr_eq(&reader.0, &container.resolve_ref::<AppConfig>()));
+    assert_eq!((reader.0.name.as_str(), editor.0.name.as_str()), ("app", "app-draft"));
+    assert_eq!(CONFIGS_BUILT.load(std::sync::atomic::Ordering::Relaxed), 1);
+}
+
+
+#[derive(Debug)]
+struct ConfigError;
+
+/// Keeps the outcome around, to report it later
+struct Loader {
+    config: Result<A, ConfigError>,
+}
+
+impl Injectable for Loader {
+    type Deps = Result<A, ConfigError>;
+
+    fn inject(config: Self::Deps) -> Self {
+        Self { config }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_result_dependencies_as_ok() {
+    let loader = Container::new().resolve::<Loader>();
+
+    assert_eq!(loader.config.map(|a| a.0).unwrap(), 1);
+}
@@ -0,0 +1,48 @@
+﻿use std::any::{type_name, TypeId};
+use std::fmt::{self, Debug};
+
+
+/// Why [`Container::resolve_checked`](super::Container::resolve_checked) failed.
+///
+/// Pure `Injectable` graphs can't fail, they're checked at compile time; the
+/// runtime registries can be missing an entry when asked, and a
+/// `TryInjectable` dep can fail to construct.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResolveError {
+    /// A `Registered<T>` dependency found no factory for `T`.
+    MissingFactory { type_id: TypeId, type_name: &'static str },
+    /// A `Bound<Tr>` dependency found nothing bound to `Tr`.
+    MissingBinding { type_id: TypeId, type_name: &'static str },
+    /// A `TryInjectable` dep's `try_inject` failed, `source` is the error's
+    /// `Debug` output. Only with the `panic-on-error` feature, which makes
+    /// such a type a dep in the first place.
+    ConstructionFailed { type_id: TypeId, type_name: &'static str, source: String },
+}
+
+impl ResolveError {
+    pub(crate) fn missing_factory<T: 'static>() -> Self {
+        Self::MissingFactory { type_id: TypeId::of::<T>(), type_name: type_name::<T>() }
+    }
+
+    pub(crate) fn missing_binding<Tr: ?Sized + 'static>() -> Self {
+        Self::MissingBinding { type_id: TypeId::of::<Tr>(), type_name: type_name::<Tr>() }
+    }
+
+    #[cfg(feature = "panic-on-error")]
+    pub(crate) fn construction_failed<T: 'static>(source: &impl Debug) -> Self {
+        Self::ConstructionFailed { type_id: TypeId::of::<T>(), type_name: type_name::<T>(), source: format!("{source:?}") }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFactory { type_name, .. } => write!(f, "no factory registered for `{type_name}`"),
+            Self::MissingBinding { type_name, .. } => write!(f, "nothing bound to `{type_name}`"),
+            Self::ConstructionFailed { type_name, source, .. } => write!(f, "failed to construct `{type_name}`: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
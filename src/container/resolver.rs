@@ -0,0 +1,48 @@
+﻿use super::resolve_deps_from::ResolveDepsFrom;
+use super::{Injectable, ResolveError};
+
+
+/// A container type the dependency machinery can build from.
+///
+/// Tuples, `Option`, arrays and the other deps made purely of `resolve` calls
+/// work with every `Resolver`, so a new container type only has to opt in:
+///
+/// ```
+/// # use singularity::container::*;
+/// # singularity::injectable!(() => Clock);
+/// # singularity::injectable!((clock: Clock, backup: Option<Clock>) => Scheduler {});
+/// struct MyContainer;
+///
+/// impl Resolver for MyContainer {}
+///
+/// let scheduler: Scheduler = MyContainer.resolve();
+/// ```
+///
+/// The default `resolve` and `resolve_checked` are the whole of it.
+/// Overriding them (to count or trace construction) takes the
+/// `ResolveDepsFrom` bound, which only this crate can name.
+pub trait Resolver: Sized {
+    /// Builds a fresh `T` from freshly resolved deps.
+    #[inline(always)]
+    fn resolve<T>(&self) -> T
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        T::inject(T::Deps::resolve_deps(self))
+    }
+
+    /// Builds a fresh `T`, a missing registration or failed construction on
+    /// the way coming back as an error instead of a panic.
+    #[inline(always)]
+    fn resolve_checked<T>(&self) -> Result<T, ResolveError>
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        T::Deps::try_resolve_deps(self).and_then(T::inject_checked)
+    }
+}
+
+#[cfg(test)]
+mod resolver_test;
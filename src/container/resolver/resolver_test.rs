@@ -0,0 +1,65 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use rstest::*;
+use super::*;
+
+
+/// A second container type: resolves like any other, counting every build.
+#[derive(Default)]
+struct CountingResolver {
+    built: Cell<usize>,
+}
+
+impl Resolver for CountingResolver {
+    fn resolve<T>(&self) -> T
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        self.built.set(self.built.get() + 1);
+        T::inject(T::Deps::resolve_deps(self))
+    }
+}
+
+
+struct Token(u8);
+struct Session(Token, Option<Token>, [Token; 2]);
+struct Gateway((Session, Token), Rc<RefCell<Token>>);
+
+impl Injectable for Token {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(1)
+    }
+}
+
+impl Injectable for Session {
+    type Deps = (Token, Option<Token>, [Token; 2]);
+
+    fn inject((token, fallback, pair): Self::Deps) -> Self {
+        Self(token, fallback, pair)
+    }
+}
+
+impl Injectable for Gateway {
+    type Deps = ((Session, Token), Rc<RefCell<Token>>);
+
+    fn inject((nested, shared): Self::Deps) -> Self {
+        Self(nested, shared)
+    }
+}
+
+
+#[rstest]
+fn it_reuses_the_dependency_machinery_for_other_resolvers() {
+    let resolver = CountingResolver::default();
+
+    let Gateway((Session(token, fallback, pair), extra), shared) = resolver.resolve();
+
+    let total = token.0 + fallback.map_or(0, |token| token.0) + pair[0].0 + pair[1].0 + extra.0 + shared.borrow().0;
+    assert_eq!(total, 6);
+    // Gateway, Session, and the six tokens
+    assert_eq!(resolver.built.get(), 8);
+}
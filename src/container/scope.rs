@@ -1,9 +1,114 @@
-﻿
+﻿use std::any::TypeId;
+use std::fmt;
+use std::str::FromStr;
+
+
+/// Lifetime of a resolved service, see [`Container::resolve_ref`](super::Container::resolve_ref).
+///
+/// Parses from and prints as `singleton`, `weak_singleton`, `transient`, `scoped`,
+/// so scopes can come from configuration.
+///
+/// Zero-sized types follow their scope like any other: their `inject` may have
+/// side effects, so a zero-sized singleton is built once, cached, disposed and
+/// dropped with the rest. [`Container::resolve_copy`](super::Container::resolve_copy)
+/// shares it with `resolve_ref` rather than keeping a copy of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Scope {
     Singleton,
+    /// Like `Singleton`, but the container only holds a `Weak` handle:
+    /// once every `Rc` is dropped the next `resolve_ref` rebuilds it.
+    WeakSingleton,
     Transient,
+    /// One instance per [`Container::child`](super::Container::child) scope.
     Scoped
 }
 
+impl Scope {
+    /// Every variant, in declaration order.
+    pub const ALL: [Scope; 4] = [Scope::Singleton, Scope::WeakSingleton, Scope::Transient, Scope::Scoped];
+
+    /// Config-friendly name of the scope.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Scope::Singleton => "singleton",
+            Scope::WeakSingleton => "weak_singleton",
+            Scope::Transient => "transient",
+            Scope::Scoped => "scoped",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+
+/// Returned when a string doesn't name a [`Scope`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseScopeError(String);
+
+impl fmt::Display for ParseScopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown scope `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseScopeError {}
+
+/// Case-insensitive, surrounding whitespace ignored.
+impl FromStr for Scope {
+    type Err = ParseScopeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        Scope::ALL
+            .into_iter()
+            .find(|scope| scope.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ParseScopeError(s.to_owned()))
+    }
+}
+
+
+/// Decides the effective scope of every type a [`Container`](super::Container) resolves.
+///
+/// `declared` is `T::SCOPE`, or the [`set_scope`](super::Container::set_scope)
+/// override if there is one. Install a policy with
+/// [`Container::with_policy`](super::Container::with_policy).
+pub trait ScopePolicy {
+    fn scope(&self, type_id: TypeId, declared: Scope) -> Scope;
+}
+
+/// The default policy: every type keeps its declared scope.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeclaredScope;
+
+impl ScopePolicy for DeclaredScope {
+    #[inline(always)]
+    fn scope(&self, _: TypeId, declared: Scope) -> Scope {
+        declared
+    }
+}
+
+/// A plain `Scope` forces itself on everything, e.g. `Scope::Transient` in tests.
+impl ScopePolicy for Scope {
+    #[inline(always)]
+    fn scope(&self, _: TypeId, _: Scope) -> Scope {
+        *self
+    }
+}
+
+impl<F> ScopePolicy for F
+where
+    F: Fn(TypeId, Scope) -> Scope,
+{
+    #[inline(always)]
+    fn scope(&self, type_id: TypeId, declared: Scope) -> Scope {
+        self(type_id, declared)
+    }
+}
 
 
+#[cfg(test)]
+mod scope_test;
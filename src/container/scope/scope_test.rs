@@ -0,0 +1,31 @@
+﻿
+
+use rstest::*;
+use super::*;
+
+
+#[rstest]
+#[case(Scope::Singleton, "singleton")]
+#[case(Scope::WeakSingleton, "weak_singleton")]
+#[case(Scope::Transient, "transient")]
+#[case(Scope::Scoped, "scoped")]
+fn it_round_trips_every_scope(#[case] scope: Scope, #[case] name: &str) {
+    assert_eq!(scope.to_string(), name);
+    assert_eq!(name.parse::<Scope>(), Ok(scope));
+    assert_eq!(scope.to_string().parse::<Scope>(), Ok(scope));
+}
+
+
+#[rstest]
+fn it_parses_case_insensitively() {
+    assert_eq!(" Singleton ".parse::<Scope>(), Ok(Scope::Singleton));
+    assert_eq!("TRANSIENT".parse::<Scope>(), Ok(Scope::Transient));
+}
+
+
+#[rstest]
+fn it_rejects_unknown_scopes() {
+    let err = "forever".parse::<Scope>().unwrap_err();
+
+    assert_eq!(err.to_string(), "unknown scope `forever`");
+}
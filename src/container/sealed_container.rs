@@ -0,0 +1,216 @@
+﻿use std::any::TypeId;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::resolve_deps_from::ResolveDepsFrom;
+use super::{AsyncInjectable, Container, Factory, Injectable, PostConstruct, ResolveError, Scope, TryInjectable};
+
+
+/// Registrations sorted by `TypeId`, looked up by bisection.
+type Table<V> = Box<[(TypeId, V)]>;
+
+/// What [`Container::seal`] froze: factories, bindings and named factories.
+pub(crate) struct Tables {
+    factories: Table<Factory>,
+    bindings: Table<Factory>,
+    named: Table<HashMap<String, Factory>>,
+}
+
+impl Tables {
+    pub(crate) fn new(container: &Container) -> Self {
+        Self {
+            factories: Self::table(&container.factories.borrow()),
+            bindings: Self::table(&container.bindings.borrow()),
+            named: Self::table(&container.named.borrow()),
+        }
+    }
+
+    fn table<V: Clone>(registry: &HashMap<TypeId, V>) -> Table<V> {
+        let mut table: Vec<_> = registry.iter().map(|(key, value)| (*key, value.clone())).collect();
+        table.sort_unstable_by_key(|&(key, _)| key);
+        table.into_boxed_slice()
+    }
+
+    fn lookup<V>(table: &Table<V>, key: TypeId) -> Option<&V> {
+        let index = table.binary_search_by_key(&key, |&(key, _)| key).ok()?;
+        Some(&table[index].1)
+    }
+
+    pub(crate) fn factory(&self, key: TypeId) -> Option<&Factory> {
+        Self::lookup(&self.factories, key)
+    }
+
+    pub(crate) fn binding(&self, key: TypeId) -> Option<&Factory> {
+        Self::lookup(&self.bindings, key)
+    }
+
+    pub(crate) fn named(&self, key: TypeId, name: &str) -> Option<&Factory> {
+        self.named_map(key)?.get(name)
+    }
+
+    pub(crate) fn named_map(&self, key: TypeId) -> Option<&HashMap<String, Factory>> {
+        Self::lookup(&self.named, key)
+    }
+}
+
+
+/// A [`Container`] whose registrations are frozen, made by [`Container::seal`].
+///
+/// It has the resolve side of `Container` only, nothing can be registered
+/// through it. Factories, bindings and named factories are copied into sorted
+/// tables that every lookup, a dependency's included, searches before the
+/// registries, without borrowing a `RefCell`. Something registered later
+/// through a handle the container gave out (an `Rc<Container>` dep) is still
+/// found, unless a table already has that type. Caches work as before.
+pub struct SealedContainer(Container);
+
+impl SealedContainer {
+    pub(crate) fn new(mut container: Container) -> Self {
+        container.sealed = Some(Rc::new(Tables::new(&container)));
+        Self(container)
+    }
+
+    /// Opens a child scope, see [`Container::child`].
+    pub fn child(&self) -> Self {
+        Self(self.0.child())
+    }
+
+    /// See [`Container::resolve`].
+    pub fn resolve<T>(&self) -> T
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve::<T>()
+    }
+
+    /// See [`Container::resolve_checked`].
+    pub fn resolve_checked<T>(&self) -> Result<T, ResolveError>
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_checked::<T>()
+    }
+
+    /// See [`Container::resolve_deduped`].
+    pub fn resolve_deduped<T>(&self) -> T
+    where
+        T: Injectable,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_deduped::<T>()
+    }
+
+    /// See [`Container::resolve_init`].
+    pub fn resolve_init<T>(&self) -> T
+    where
+        T: PostConstruct,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_init::<T>()
+    }
+
+    /// See [`Container::resolve_async`].
+    pub async fn resolve_async<T>(&self) -> T
+    where
+        T: AsyncInjectable,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_async::<T>().await
+    }
+
+    /// See [`Container::try_resolve`].
+    pub fn try_resolve<T>(&self) -> Result<T, T::Error>
+    where
+        T: TryInjectable,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.try_resolve::<T>()
+    }
+
+    /// See [`Container::resolve_ref`].
+    pub fn resolve_ref<T>(&self) -> Rc<T>
+    where
+        T: Injectable + 'static,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_ref::<T>()
+    }
+
+    /// See [`Container::resolve_scoped`].
+    pub fn resolve_scoped<T>(&self) -> Rc<T>
+    where
+        T: Injectable + 'static,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_scoped::<T>()
+    }
+
+    /// See [`Container::resolve_vec`].
+    pub fn resolve_vec<T>(&self, n: usize) -> Vec<T>
+    where
+        T: Injectable + Clone + 'static,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_vec::<T>(n)
+    }
+
+    /// See [`Container::resolve_copy`].
+    pub fn resolve_copy<T>(&self) -> T
+    where
+        T: Injectable + Copy + 'static,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.resolve_copy::<T>()
+    }
+
+    /// See [`Container::borrow`].
+    #[allow(clippy::should_implement_trait)] // generic over `T`, not `Borrow`
+    pub fn borrow<T>(&self) -> &T
+    where
+        T: Injectable + 'static,
+        T::Deps: ResolveDepsFrom<Container>,
+    {
+        self.0.borrow::<T>()
+    }
+
+    /// See [`Container::resolve_slice`].
+    pub fn resolve_slice<P: 'static>(&self) -> &[P] {
+        self.0.resolve_slice::<P>()
+    }
+
+    /// See [`Container::resolve_registered`].
+    pub fn resolve_registered<T: 'static>(&self) -> Option<T> {
+        self.0.resolve_registered::<T>()
+    }
+
+    /// See [`Container::resolve_bound`].
+    pub fn resolve_bound<Tr: ?Sized + 'static>(&self) -> Option<Box<Tr>> {
+        self.0.resolve_bound::<Tr>()
+    }
+
+    /// See [`Container::resolve_named`].
+    pub fn resolve_named<T: 'static>(&self, name: &str) -> Option<T> {
+        self.0.resolve_named::<T>(name)
+    }
+
+    /// See [`Container::resolve_named_map`].
+    pub fn resolve_named_map<T: 'static>(&self) -> HashMap<String, T> {
+        self.0.resolve_named_map::<T>()
+    }
+
+    /// See [`Container::is_registered`].
+    pub fn is_registered<T: 'static>(&self) -> bool {
+        self.0.is_registered::<T>()
+    }
+
+    /// See [`Container::scope_of`].
+    pub fn scope_of<T: Injectable + 'static>(&self) -> Scope {
+        self.0.scope_of::<T>()
+    }
+}
+
+
+#[cfg(test)]
+mod sealed_container_test;
@@ -0,0 +1,152 @@
+use rstest::*;
+use super::*;
+use super::super::{Bound, Preferred, Registered};
+
+
+#[derive(Clone)]
+struct Endpoint(String);
+
+trait Greeter {
+    fn greet(&self) -> String;
+}
+
+struct English;
+
+impl Injectable for English {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self
+    }
+}
+
+impl Greeter for English {
+    fn greet(&self) -> String {
+        "hello".into()
+    }
+}
+
+struct Pool(u8);
+
+impl Injectable for Pool {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(4)
+    }
+}
+
+struct Client {
+    endpoint: Registered<Endpoint>,
+    greeter: Bound<dyn Greeter>,
+    missing: Option<Registered<u64>>,
+}
+
+impl Injectable for Client {
+    type Deps = (Registered<Endpoint>, Bound<dyn Greeter>, Option<Registered<u64>>);
+
+    fn inject((endpoint, greeter, missing): Self::Deps) -> Self {
+        Self { endpoint, greeter, missing }
+    }
+}
+
+fn registered() -> Container {
+    let container = Container::new();
+    container.register_instance(Endpoint("db:5432".into()));
+    container.register_factory(|_| 7u32);
+    container.bind::<dyn Greeter, English>(|imp| imp);
+    container.register_named("primary", |_| 5432u16);
+    container.register_named("replica", |_| 5433u16);
+    container
+}
+
+fn registered_without_greeter() -> Container {
+    let container = Container::new();
+    container.register_instance(Endpoint("db:5432".into()));
+    container
+}
+
+
+#[rstest]
+fn it_resolves_the_same_after_sealing() {
+    let open = registered();
+    let sealed = registered().seal();
+
+    let (before, after) = (open.resolve::<Client>(), sealed.resolve::<Client>());
+    assert_eq!(before.endpoint.0.0, after.endpoint.0.0);
+    assert_eq!(before.greeter.greet(), after.greeter.greet());
+    assert_eq!(before.missing.is_none(), after.missing.is_none());
+    assert_eq!(open.resolve_registered::<u32>(), sealed.resolve_registered::<u32>());
+    assert_eq!(open.resolve_named::<u16>("replica"), sealed.resolve_named::<u16>("replica"));
+    assert_eq!(open.resolve_named_map::<u16>(), sealed.resolve_named_map::<u16>());
+    assert_eq!(sealed.resolve_named_map::<u16>().len(), 2);
+
+    assert!(sealed.is_registered::<Endpoint>());
+    assert!(!sealed.is_registered::<u64>());
+    assert!(sealed.resolve_bound::<dyn Fn()>().is_none());
+}
+
+
+#[rstest]
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+fn it_keeps_caching_singletons_once_sealed() {
+    let sealed = registered().seal();
+
+    let first = sealed.resolve_ref::<Pool>();
+    assert!(std::rc::Rc::ptr_eq(&first, &sealed.resolve_ref::<Pool>()));
+    assert_eq!(first.0, 4);
+}
+
+
+#[derive(Clone)]
+struct Label(&'static str);
+
+impl Injectable for Label {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self("declared")
+    }
+}
+
+/// Reaches the sealed tables only through its deps
+struct Frontend {
+    pool: Rc<Pool>,
+    endpoint: Preferred<Label>,
+    client: Rc<Client>,
+}
+
+impl Injectable for Frontend {
+    type Deps = (Rc<Pool>, Preferred<Label>, Rc<Client>);
+
+    fn inject((pool, endpoint, client): Self::Deps) -> Self {
+        Self { pool, endpoint, client }
+    }
+}
+
+
+#[rstest]
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+fn it_resolves_deps_through_the_sealed_tables() {
+    let container = registered();
+    container.register_instance(Label("registered"));
+    container.register_named("primary", |_| Label("primary"));
+    let sealed = container.seal();
+
+    let frontend = sealed.resolve::<Frontend>();
+    assert!(Rc::ptr_eq(&frontend.pool, &sealed.resolve_ref::<Pool>()));
+    assert_eq!(frontend.endpoint.0.0, "registered");
+    assert_eq!(frontend.client.endpoint.0.0, "db:5432");
+
+    assert_eq!(sealed.resolve_named::<Label>("primary").map(|label| label.0), Some("primary"));
+    assert_eq!(sealed.resolve_named_map::<Label>().len(), 1);
+    assert_eq!(sealed.borrow::<Pool>().0, 4);
+
+    let child = sealed.child();
+    assert_eq!(child.resolve_checked::<Client>().map(|client| client.greeter.greet()).ok().as_deref(), Some("hello"));
+    assert_eq!(
+        registered_without_greeter().seal().resolve_checked::<Client>().err(),
+        Some(ResolveError::missing_binding::<dyn Greeter>()),
+    );
+}
@@ -0,0 +1,85 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use rstest::*;
+use super::*;
+
+
+/// Counts allocations made on the current thread, tests run in parallel.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+
+#[derive(Clone, Copy)]
+struct Settings(u32);
+struct Driver(Settings);
+struct Board(Driver, Settings);
+
+impl Injectable for Settings {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(115_200)
+    }
+}
+
+impl Injectable for Driver {
+    type Deps = Settings;
+
+    fn inject(settings: Self::Deps) -> Self {
+        Self(settings)
+    }
+}
+
+impl Injectable for Board {
+    type Deps = (Driver, Settings);
+
+    fn inject((driver, settings): Self::Deps) -> Self {
+        Self(driver, settings)
+    }
+}
+
+
+#[rstest]
+fn it_resolves_without_allocating() {
+    let container = Container::new();
+
+    let before = allocations();
+    let board = container.resolve::<Board>();
+    let settings = container.resolve_copy::<Settings>();
+    assert_eq!(allocations(), before);
+
+    assert_eq!(board.0.0.0, 115_200);
+    assert_eq!(board.1.0, settings.0);
+}
+
+#[rstest]
+fn it_ignores_every_scope() {
+    let container = Container::new();
+    let child = container.child();
+
+    assert_eq!(container.scope_of::<Settings>(), Scope::Transient);
+    assert!(!Rc::ptr_eq(&child.resolve_ref::<Settings>(), &child.resolve_ref::<Settings>()));
+    assert!(!Rc::ptr_eq(&child.resolve_scoped::<Driver>(), &child.resolve_scoped::<Driver>()));
+}
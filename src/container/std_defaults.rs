@@ -0,0 +1,41 @@
+﻿
+
+/// `Injectable` for common std types, built via `Default`.
+/// Saves a factory closure for every config-ish `String` or `Vec` dependency.
+///
+/// `Option<T>` is intentionally absent: it already resolves as an optional
+/// dependency (`Some(resolve::<T>())`) and both impls can't coexist.
+macro_rules! injectable_via_default {
+    ( $( $T:ty ),+ $(,)? ) => {
+        $(
+            impl super::Injectable for $T {
+                type Deps = ();
+                #[inline(always)]
+                fn inject(_: Self::Deps) -> Self {
+                    Default::default()
+                }
+            }
+        )+
+    };
+}
+
+
+injectable_via_default!(
+    String,
+    bool,
+    u8, u16, u32, u64, u128, usize,
+    i8, i16, i32, i64, i128, isize,
+    f32, f64,
+);
+
+impl<T> super::Injectable for Vec<T> {
+    type Deps = ();
+    #[inline(always)]
+    fn inject(_: Self::Deps) -> Self {
+        Vec::new()
+    }
+}
+
+
+#[cfg(test)]
+mod std_defaults_test;
@@ -0,0 +1,29 @@
+﻿
+
+use rstest::*;
+use super::super::{Container, Injectable};
+
+
+struct Settings {
+    name: String,
+    ports: Vec<i32>,
+    retries: u8,
+}
+
+impl Injectable for Settings {
+    type Deps = (String, Vec<i32>, u8);
+
+    fn inject((name, ports, retries): Self::Deps) -> Self {
+        Self { name, ports, retries }
+    }
+}
+
+
+#[rstest]
+fn it_resolves_std_types_from_default() {
+    let settings = Container::new().resolve::<Settings>();
+
+    assert_eq!(settings.name, String::new());
+    assert!(settings.ports.is_empty());
+    assert_eq!(settings.retries, 0);
+}
@@ -0,0 +1,74 @@
+﻿use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use super::cache::{get_or_build, SyncCached};
+use super::resolve_deps_from::ResolveDepsFrom;
+use super::{Injectable, Resolver, Scope};
+
+
+/// Thread-safe counterpart of [`Container`](super::Container), for services
+/// shared across threads as `Arc<T>`.
+///
+/// Deliberately small: no runtime registries or child scopes, a type's scope
+/// is its `T::SCOPE`.
+#[derive(Default)]
+pub struct SyncContainer {
+    /// Shared instances handed out by `resolve_arc`.
+    singletons: Mutex<HashMap<TypeId, SyncCached>>,
+}
+
+impl SyncContainer {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves a shared handle to `T`, the thread-safe analog of
+    /// [`Container::resolve_ref`](super::Container::resolve_ref).
+    ///
+    /// `Singleton`s are kept alive by the container, `WeakSingleton`s only by
+    /// the handles out there; `Scoped` and `Transient` build fresh. Two threads racing on the first call may both build
+    /// `T`, but only one instance is ever handed out.
+    pub fn resolve_arc<T>(&self) -> Arc<T>
+    where
+        T: Injectable + Send + Sync + 'static,
+        T::Deps: ResolveDepsFrom<Self>,
+    {
+        let scope = if cfg!(feature = "static-container") { Scope::Transient } else { T::SCOPE };
+        if !matches!(scope, Scope::Singleton | Scope::WeakSingleton) {
+            return Arc::new(self.resolve::<T>());
+        }
+
+        let key = TypeId::of::<T>();
+        let Ok(instance) = get_or_build::<_, Infallible>(
+            || self.singletons().get(&key).and_then(SyncCached::upgrade),
+            || Ok(Arc::new(self.resolve::<T>()) as Arc<dyn Any + Send + Sync>),
+            |built| {
+                let mut singletons = self.singletons();
+                // First one in wins, a racing thread's instance is dropped
+                if let Some(winner) = singletons.get(&key).and_then(SyncCached::upgrade) {
+                    return winner;
+                }
+                let entry = match scope {
+                    Scope::WeakSingleton => SyncCached::Weak(Arc::downgrade(&built)),
+                    _ => SyncCached::Strong(built.clone()),
+                };
+                singletons.insert(key, entry);
+                built
+            },
+        );
+        instance.downcast::<T>().expect("singleton cache type mismatch")
+    }
+
+    /// The cache is never left half-updated, so a poisoned lock is still usable.
+    fn singletons(&self) -> MutexGuard<'_, HashMap<TypeId, SyncCached>> {
+        self.singletons.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Resolver for SyncContainer {}
+
+#[cfg(test)]
+mod sync_container_test;
@@ -0,0 +1,76 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use rstest::*;
+use super::*;
+
+
+static BUILT: AtomicUsize = AtomicUsize::new(0);
+
+struct HitCounter {
+    hits: AtomicUsize,
+}
+
+impl Injectable for HitCounter {
+    type Deps = ();
+    const SCOPE: Scope = Scope::Singleton;
+
+    fn inject(_: Self::Deps) -> Self {
+        BUILT.fetch_add(1, Ordering::SeqCst);
+        Self { hits: AtomicUsize::new(0) }
+    }
+}
+
+struct Request(usize);
+
+impl Injectable for Request {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self(7)
+    }
+}
+
+struct Handler(Request, Request);
+
+impl Injectable for Handler {
+    type Deps = (Request, Request);
+
+    fn inject((first, second): Self::Deps) -> Self {
+        Self(first, second)
+    }
+}
+
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<SyncContainer>();
+};
+
+
+#[cfg_attr(feature = "static-container", ignore = "the static container caches nothing")]
+#[rstest]
+fn it_shares_arc_singletons_across_threads() {
+    let container = SyncContainer::new();
+    let counter = container.resolve_arc::<HitCounter>();
+
+    thread::scope(|threads| {
+        for _ in 0..8 {
+            let counter = counter.clone();
+            threads.spawn(move || counter.hits.fetch_add(1, Ordering::SeqCst));
+            threads.spawn(|| container.resolve_arc::<HitCounter>().hits.fetch_add(1, Ordering::SeqCst));
+        }
+    });
+
+    assert_eq!(counter.hits.load(Ordering::SeqCst), 16);
+    assert!(Arc::ptr_eq(&counter, &container.resolve_arc::<HitCounter>()));
+    assert_eq!(BUILT.load(Ordering::SeqCst), 1);
+}
+
+#[rstest]
+fn it_resolves_dependencies_by_value() {
+    let container = SyncContainer::new();
+
+    let handler = container.resolve::<Handler>();
+    assert_eq!(handler.0.0 + handler.1.0, 14);
+    assert!(!Arc::ptr_eq(&container.resolve_arc::<Request>(), &container.resolve_arc::<Request>()));
+}
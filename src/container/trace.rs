@@ -0,0 +1,26 @@
+﻿use std::cell::Cell;
+
+
+thread_local! {
+    /// How many `resolve` calls are underway on this thread.
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// One level of resolution, left again on drop (unwinding included).
+pub(crate) struct Depth(());
+
+/// Logs that `T` is being resolved, indented two spaces per enclosing `resolve`.
+pub(crate) fn enter<T>() -> Depth {
+    let depth = DEPTH.with(|depth| depth.replace(depth.get() + 1));
+    log::trace!("{:indent$}resolving {}", "", std::any::type_name::<T>(), indent = depth * 2);
+    Depth(())
+}
+
+impl Drop for Depth {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+#[cfg(test)]
+mod trace_test;
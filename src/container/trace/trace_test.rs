@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+use std::sync::Once;
+
+use rstest::*;
+use super::super::*;
+
+
+thread_local! {
+    /// Lines logged by this thread, tests run in parallel.
+    static LINES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+struct CapturingLogger;
+
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Trace
+    }
+
+    fn log(&self, record: &log::Record) {
+        LINES.with(|lines| lines.borrow_mut().push(record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+fn capture() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        log::set_logger(&CapturingLogger).expect("no other logger in this test binary");
+        log::set_max_level(log::LevelFilter::Trace);
+    });
+    LINES.with(|lines| lines.borrow_mut().clear());
+}
+
+
+struct Config;
+struct Repository(Config);
+struct Service(Repository, Config);
+
+impl Injectable for Config {
+    type Deps = ();
+
+    fn inject(_: Self::Deps) -> Self {
+        Self
+    }
+}
+
+impl Injectable for Repository {
+    type Deps = Config;
+
+    fn inject(config: Self::Deps) -> Self {
+        Self(config)
+    }
+}
+
+impl Injectable for Service {
+    type Deps = (Repository, Config);
+
+    fn inject((repository, config): Self::Deps) -> Self {
+        Self(repository, config)
+    }
+}
+
+
+#[rstest]
+fn it_logs_resolutions_indented_by_depth() {
+    capture();
+
+    Container::new().resolve::<Service>();
+
+    let lines = LINES.with(|lines| lines.take());
+    let short: Vec<_> = lines.iter().map(|line| line.replace(module_path!(), "")).collect();
+    assert_eq!(short, [
+        "resolving ::Service",
+        "  resolving ::Repository",
+        "    resolving ::Config",
+        "  resolving ::Config",
+    ]);
+}
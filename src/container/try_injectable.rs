@@ -0,0 +1,43 @@
+﻿use std::fmt::Debug;
+
+use super::scope::Scope;
+
+
+/// Like [`Injectable`](super::Injectable), for services whose construction can fail.
+///
+/// Resolve through [`Container::try_resolve`](super::Container::try_resolve) to get
+/// the error back. With the `panic-on-error` feature every `TryInjectable` is also
+/// `Injectable`, so it can be a plain dependency and a failure panics instead.
+pub trait TryInjectable: Sized {
+    type Deps;
+    type Error: Debug;
+    const SCOPE: Scope = Scope::Scoped;
+    /// See [`Injectable::DEP_COUNT`](super::Injectable::DEP_COUNT).
+    const DEP_COUNT: usize = 0;
+    fn try_inject(deps: Self::Deps) -> Result<Self, Self::Error>;
+}
+
+
+/// Prototyping bridge: unwraps `try_inject`, naming the failed type in the panic.
+/// [`Container::resolve_checked`](super::Container::resolve_checked) gets the
+/// failure back as [`ResolveError::ConstructionFailed`](super::ResolveError::ConstructionFailed).
+///
+/// Goes through `Injectable` rather than `ResolveDepsFrom` directly, which would
+/// overlap with the blanket impl for every `Injectable`.
+#[cfg(feature = "panic-on-error")]
+impl<A: TryInjectable + 'static> super::Injectable for A {
+    type Deps = <A as TryInjectable>::Deps;
+    const SCOPE: Scope = <A as TryInjectable>::SCOPE;
+    const DEP_COUNT: usize = <A as TryInjectable>::DEP_COUNT;
+
+    fn inject(deps: Self::Deps) -> Self {
+        Self::inject_checked(deps).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    fn inject_checked(deps: Self::Deps) -> Result<Self, super::ResolveError> {
+        A::try_inject(deps).map_err(|err| super::ResolveError::construction_failed::<A>(&err))
+    }
+}
+
+#[cfg(test)]
+mod try_injectable_test;
@@ -0,0 +1,83 @@
+﻿
+
+use rstest::*;
+use super::*;
+use crate::container::{Container, Registered};
+
+
+/// Refuses to bind to port 0.
+#[derive(Debug)]
+struct Listener(u16);
+
+impl TryInjectable for Listener {
+    type Deps = Registered<u16>;
+    type Error = String;
+
+    fn try_inject(port: Self::Deps) -> Result<Self, Self::Error> {
+        match port.into_inner() {
+            0 => Err("port 0 is not allowed".into()),
+            port => Ok(Self(port)),
+        }
+    }
+}
+
+
+/// Container handing out `port` to every `Listener`.
+fn with_port(port: u16) -> Container {
+    let container = Container::new();
+    container.register_instance(port);
+    container
+}
+
+
+#[rstest]
+fn it_resolves_fallible_services() {
+    assert_eq!(with_port(8080).try_resolve::<Listener>().unwrap().0, 8080);
+}
+
+
+#[rstest]
+fn it_returns_the_construction_error() {
+    let err = with_port(0).try_resolve::<Listener>().unwrap_err();
+
+    assert_eq!(err, "port 0 is not allowed");
+}
+
+
+#[cfg(feature = "panic-on-error")]
+struct Server(#[allow(dead_code)] Listener);
+
+#[cfg(feature = "panic-on-error")]
+impl crate::container::Injectable for Server {
+    type Deps = Listener;
+
+    fn inject(deps: Self::Deps) -> Self {
+        Self(deps)
+    }
+}
+
+
+#[cfg(feature = "panic-on-error")]
+#[rstest]
+#[should_panic(expected = "failed to construct `singularity::container::try_injectable::try_injectable_test::Listener`: \"port 0 is not allowed\"")]
+fn it_panics_with_the_type_name_when_a_dependency_fails() {
+    with_port(0).resolve::<Server>();
+}
+
+
+#[cfg(feature = "panic-on-error")]
+#[rstest]
+fn it_reports_a_failed_dependency_as_construction_failed() {
+    use std::any::TypeId;
+    use crate::container::ResolveError;
+
+    let error = with_port(0).resolve_checked::<Server>().err().expect("port 0 is refused");
+
+    assert!(matches!(
+        &error,
+        ResolveError::ConstructionFailed { type_id, source, .. }
+            if *type_id == TypeId::of::<Listener>() && source == "\"port 0 is not allowed\""
+    ));
+    assert_eq!(error, ResolveError::construction_failed::<Listener>(&"port 0 is not allowed"));
+    assert!(with_port(8080).resolve_checked::<Server>().is_ok());
+}
@@ -0,0 +1,49 @@
+﻿use std::fmt;
+use std::marker::PhantomData;
+
+
+/// Names the variant the `#[derive(Injectable)]` enum `E` resolves as.
+///
+/// The enum asks for it as a [`Registered`](super::Registered) dependency,
+/// so it's decided at runtime, by whoever registers it:
+/// `container.register_instance(VariantSelector::<Storage>::new("Postgres"))`.
+/// Keyed by `E`, so every derived enum in a container has its own selector.
+pub struct VariantSelector<E> {
+    variant: String,
+    enum_type: PhantomData<fn() -> E>,
+}
+
+impl<E> VariantSelector<E> {
+    /// Selects the variant called `variant`, spelled as declared.
+    pub fn new(variant: impl Into<String>) -> Self {
+        Self { variant: variant.into(), enum_type: PhantomData }
+    }
+
+    /// The selected variant's name.
+    #[inline(always)]
+    pub fn variant(&self) -> &str {
+        &self.variant
+    }
+}
+
+// Written out, derives would bound `E` on traits it never needs
+
+impl<E> Clone for VariantSelector<E> {
+    fn clone(&self) -> Self {
+        Self::new(self.variant.clone())
+    }
+}
+
+impl<E> fmt::Debug for VariantSelector<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VariantSelector").field(&self.variant).finish()
+    }
+}
+
+impl<E> PartialEq for VariantSelector<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.variant == other.variant
+    }
+}
+
+impl<E> Eq for VariantSelector<E> {}
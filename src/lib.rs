@@ -1,3 +1,55 @@
 pub mod container;
+pub mod testing;
 
+pub use container::Scope;
 
+
+
+/// Resolves several services at once, left to right, into a tuple.
+///
+/// ```
+/// # use singularity::container::*;
+/// # use singularity::resolve_all;
+/// # singularity::injectable!(() => Parse);
+/// # singularity::injectable!(() => Validate);
+/// # singularity::injectable!(() => Store);
+/// # let container = Container::new();
+/// let (parse, validate, store) = resolve_all!(container, Parse, Validate, Store);
+/// ```
+#[macro_export]
+macro_rules! resolve_all {
+    ($container:expr, $($T:ty),+ $(,)?) => {{
+        let container = &$container;
+        ($( container.resolve::<$T>(), )+)
+    }};
+}
+
+
+/// Fails to compile unless every listed service resolves from a
+/// [`Container`](container::Container), reporting a wiring gap where the
+/// service is defined instead of at some far-away `resolve` call.
+///
+/// Expands to a `const` item, so it goes anywhere an item does.
+///
+/// ```
+/// use singularity::assert_injectable;
+/// use singularity::container::Injectable;
+///
+/// struct Clock;
+///
+/// impl Injectable for Clock {
+///     type Deps = ();
+///
+///     fn inject(_: Self::Deps) -> Self {
+///         Self
+///     }
+/// }
+///
+/// assert_injectable!(Clock);
+/// ```
+#[macro_export]
+macro_rules! assert_injectable {
+    ($($T:ty),+ $(,)?) => {
+        const _: () = { $( $crate::testing::assert_resolves::<$T>(); )+ };
+    };
+}
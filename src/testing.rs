@@ -0,0 +1,57 @@
+﻿//! Helpers for tests that exercise a dependency graph.
+
+use crate::container::{Container, Injectable, ResolveDepsFrom, Scope};
+
+
+/// Fails to compile unless `T` can be resolved from a [`Container`], so a
+/// broken graph shows up as a build error rather than a failing test.
+///
+/// ```
+/// use singularity::container::Injectable;
+/// use singularity::testing::assert_resolves;
+///
+/// struct Clock;
+///
+/// impl Injectable for Clock {
+///     type Deps = ();
+///
+///     fn inject(_: Self::Deps) -> Self {
+///         Self
+///     }
+/// }
+///
+/// const _: () = assert_resolves::<Clock>();
+/// ```
+pub const fn assert_resolves<T>()
+where
+    T: Injectable,
+    T::Deps: ResolveDepsFrom<Container>,
+{
+}
+
+/// Resolves `T` from a fresh container whose every scope is forced to
+/// `Transient`, so nothing cached leaks between tests.
+///
+/// ```
+/// use singularity::container::Injectable;
+/// use singularity::testing::resolve_for_test;
+///
+/// struct Port(u16);
+///
+/// impl Injectable for Port {
+///     type Deps = ();
+///
+///     fn inject(_: Self::Deps) -> Self {
+///         Self(8080)
+///     }
+/// }
+///
+/// assert_eq!(resolve_for_test::<Port>().0, 8080);
+/// ```
+pub fn resolve_for_test<T>() -> T
+where
+    T: Injectable,
+    T::Deps: ResolveDepsFrom<Container>,
+{
+    Container::with_policy(Scope::Transient).resolve::<T>()
+}